@@ -0,0 +1,24 @@
+#ifndef RRAYLIB_ALLOC_H
+#define RRAYLIB_ALLOC_H
+
+#include <stddef.h>
+
+// Implemented in src/memory.rs on top of Rust's global allocator.
+void *rraylib_malloc(size_t size);
+void *rraylib_calloc(size_t count, size_t size);
+void *rraylib_realloc(void *ptr, size_t size);
+void rraylib_free(void *ptr);
+
+#define RL_MALLOC(sz)           rraylib_malloc(sz)
+#define RL_CALLOC(n, sz)        rraylib_calloc(n, sz)
+#define RL_REALLOC(ptr, sz)     rraylib_realloc(ptr, sz)
+#define RL_FREE(ptr)            rraylib_free(ptr)
+
+#define RAYGUI_MALLOC(sz)       rraylib_malloc(sz)
+#define RAYGUI_CALLOC(n, sz)    rraylib_calloc(n, sz)
+#define RAYGUI_FREE(ptr)        rraylib_free(ptr)
+
+#define PHYSAC_MALLOC(sz)       rraylib_malloc(sz)
+#define PHYSAC_FREE(ptr)        rraylib_free(ptr)
+
+#endif
@@ -1,7 +1,7 @@
 use crate::pkg::*;
 use std::fs::File;
 use std::io::Write;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 mod pkg;
 
@@ -84,7 +84,9 @@ fn main() -> anyhow::Result<()> {
         config.define("OPENGL_VERSION", "4.3");
     }
 
-    if cfg!(feature = "opengl_33") {
+    // 3.3 is a default feature, so enabling 4.3 must not need
+    // `default-features = false` to take effect.
+    if cfg!(feature = "opengl_33") && !cfg!(feature = "opengl_43") {
         config.define("OPENGL_VERSION", "3.3");
     }
 
@@ -104,18 +106,32 @@ fn main() -> anyhow::Result<()> {
         config.define("OPENGL_ES_VERSION", "ES 2.0");
     }
 
-    if cfg!(feature = "wayland") && !cfg!(feature = "x11") && !cfg!(feature = "sdl") {
+    // Headless builds bring up no windowing system, so they take priority
+    // over the platform features.
+    let headless = cfg!(feature = "headless");
+
+    if headless {
+        // GLFW is built with no windowing backend, leaving only its null
+        // platform, which `WindowBuilder::build` requests explicitly. Its
+        // OpenGL contexts are created with OSMesa, loaded at runtime.
+        config
+            .define("PLATFORM", "Desktop")
+            .define("GLFW_BUILD_X11", "OFF")
+            .define("GLFW_BUILD_WAYLAND", "OFF");
+    }
+
+    if !headless && cfg!(feature = "wayland") && !cfg!(feature = "x11") && !cfg!(feature = "sdl") {
         config.define("PLATFORM", "Desktop");
         config.define("GLFW_BUILD_WAYLAND", "ON");
     }
 
-    if cfg!(feature = "x11") && !cfg!(feature = "wayland") && !cfg!(feature = "sdl") {
+    if !headless && cfg!(feature = "x11") && !cfg!(feature = "wayland") && !cfg!(feature = "sdl") {
         config
             .define("PLATFORM", "Desktop")
             .define("GLFW_BUILD_X11", "ON");
     }
 
-    if cfg!(feature = "sdl") && !cfg!(feature = "wayland") && !cfg!(feature = "x11") {
+    if !headless && cfg!(feature = "sdl") && !cfg!(feature = "wayland") && !cfg!(feature = "x11") {
         config
             .define("PLATFORM", "SDL")
             .define("OPENGL_VERSION", "OFF");
@@ -125,6 +141,10 @@ fn main() -> anyhow::Result<()> {
         config.define("USE_EXTERNAL_GLFW", "ON");
     }
 
+    if cfg!(feature = "rust_alloc") {
+        config.cflag(force_include(&alloc_header()));
+    }
+
     let out_dir = config.build();
     let search_dirs = vec!["lib64", "lib32", "lib", "bin"];
     for search_dir in search_dirs {
@@ -139,7 +159,13 @@ fn main() -> anyhow::Result<()> {
     println!("cargo:rustc-link-lib=static=raylib");
 
     if cfg!(feature = "raygui") {
-        cc::Build::new()
+        let mut build = cc::Build::new();
+
+        if cfg!(feature = "rust_alloc") {
+            build.flag(force_include(&alloc_header()));
+        }
+
+        build
             .include(&raylib_src)
             .include(raygui_src)
             .warnings(false)
@@ -153,7 +179,13 @@ fn main() -> anyhow::Result<()> {
     }
 
     if cfg!(feature = "physac") {
-        cc::Build::new()
+        let mut build = cc::Build::new();
+
+        if cfg!(feature = "rust_alloc") {
+            build.flag(force_include(&alloc_header()));
+        }
+
+        build
             .include(raylib_src)
             .include(physac_src)
             .warnings(false)
@@ -168,3 +200,19 @@ fn main() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+/// Path to the header that redirects raylib's allocation macros to Rust.
+fn alloc_header() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("build")
+        .join("alloc.h")
+}
+
+/// Builds a compiler flag that includes `header` at the top of every unit.
+fn force_include(header: &Path) -> String {
+    if std::env::var("CARGO_CFG_TARGET_ENV").as_deref() == Ok("msvc") {
+        format!("/FI{}", header.display())
+    } else {
+        format!("-include{}", header.display())
+    }
+}
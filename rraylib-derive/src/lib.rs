@@ -0,0 +1,216 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, quote_spanned};
+use syn::spanned::Spanned;
+use syn::{parse_macro_input, Data, DeriveInput, Fields, LitStr};
+
+/// Derives `rraylib::shader::ShaderUniforms` for a struct whose fields all
+/// implement `rraylib::shader::ShaderUniform`.
+///
+/// Each field is uploaded to the uniform of the same name. Use
+/// `#[uniform(name = "...")]` to upload to a differently named uniform, or
+/// `#[uniform(skip)]` to leave a field out.
+#[proc_macro_derive(ShaderUniforms, attributes(uniform))]
+pub fn derive_shader_uniforms(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+struct Uniform {
+    field: syn::Ident,
+    ty: syn::Type,
+    name: String,
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    &input.ident,
+                    "ShaderUniforms can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "ShaderUniforms can only be derived for structs",
+            ))
+        }
+    };
+
+    let mut uniforms = Vec::new();
+
+    for field in fields {
+        let ident = field.ident.clone().expect("named field");
+        let mut name = ident.to_string();
+        let mut skip = false;
+
+        for attr in field.attrs.iter().filter(|a| a.path().is_ident("uniform")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("name") {
+                    name = meta.value()?.parse::<LitStr>()?.value();
+                    Ok(())
+                } else if meta.path.is_ident("skip") {
+                    skip = true;
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `name = \"...\"` or `skip`"))
+                }
+            })?;
+        }
+
+        if !skip {
+            uniforms.push(Uniform {
+                field: ident,
+                ty: field.ty.clone(),
+                name,
+            });
+        }
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let count = uniforms.len();
+    let names = uniforms.iter().map(|u| &u.name);
+    // Spanning each upload by its field's type points errors about types
+    // that aren't uniforms at the offending field.
+    let uploads = uniforms.iter().enumerate().map(|(index, u)| {
+        let field = &u.field;
+        quote_spanned! {u.ty.span()=>
+            shader.set_at(locations[#index], &self.#field);
+        }
+    });
+
+    Ok(quote! {
+        impl #impl_generics ::rraylib::shader::ShaderUniforms for #ident #ty_generics #where_clause {
+            type Locations = [i32; #count];
+
+            #[allow(unused_variables)]
+            fn resolve(
+                shader: &::rraylib::shader::OwnedShader,
+            ) -> ::rraylib::Result<Self::Locations> {
+                Ok([#(shader.location(#names)?),*])
+            }
+
+            #[allow(unused_variables)]
+            fn apply_resolved(
+                &self,
+                shader: &::rraylib::shader::OwnedShader,
+                locations: &Self::Locations,
+            ) {
+                #(#uploads)*
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    fn expand_to_string(input: DeriveInput) -> String {
+        expand(input).unwrap().to_string()
+    }
+
+    fn expand_error(input: DeriveInput) -> String {
+        expand(input).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn named_fields_map_to_uniforms() {
+        let output = expand_to_string(parse_quote! {
+            struct Material {
+                tint: Color,
+                #[uniform(name = "u_time")]
+                time: f32,
+                #[uniform(skip)]
+                label: String,
+            }
+        });
+
+        assert!(output.contains("for Material"));
+        assert!(output.contains("type Locations = [i32 ; 2usize]"));
+        assert!(output.contains(r#"shader . location ("tint") ?"#));
+        assert!(output.contains(r#"shader . location ("u_time") ?"#));
+        assert!(output.contains("& self . time"));
+        assert!(!output.contains("label"));
+    }
+
+    #[test]
+    fn generics_are_forwarded() {
+        let output = expand_to_string(parse_quote! {
+            struct Wrapper<T: Copy> where T: Default {
+                value: T,
+            }
+        });
+
+        assert!(output.contains("impl < T : Copy >"));
+        assert!(output.contains("for Wrapper < T > where T : Default"));
+    }
+
+    #[test]
+    fn empty_struct_has_no_locations() {
+        let output = expand_to_string(parse_quote! {
+            struct Empty {}
+        });
+
+        assert!(output.contains("type Locations = [i32 ; 0usize]"));
+    }
+
+    #[test]
+    fn enums_are_rejected() {
+        let error = expand_error(parse_quote! {
+            enum Mode { A, B }
+        });
+
+        assert_eq!(error, "ShaderUniforms can only be derived for structs");
+    }
+
+    #[test]
+    fn unions_are_rejected() {
+        let error = expand_error(parse_quote! {
+            union Bits { f: f32, i: i32 }
+        });
+
+        assert_eq!(error, "ShaderUniforms can only be derived for structs");
+    }
+
+    #[test]
+    fn tuple_and_unit_structs_are_rejected() {
+        let expected = "ShaderUniforms can only be derived for structs with named fields";
+
+        assert_eq!(expand_error(parse_quote! { struct Pair(f32, f32); }), expected);
+        assert_eq!(expand_error(parse_quote! { struct Unit; }), expected);
+    }
+
+    #[test]
+    fn unknown_attributes_are_rejected() {
+        let error = expand_error(parse_quote! {
+            struct Material {
+                #[uniform(rename = "u_tint")]
+                tint: Color,
+            }
+        });
+
+        assert_eq!(error, "expected `name = \"...\"` or `skip`");
+    }
+
+    #[test]
+    fn non_string_names_are_rejected() {
+        let error = expand_error(parse_quote! {
+            struct Material {
+                #[uniform(name = 3)]
+                tint: Color,
+            }
+        });
+
+        assert_eq!(error, "expected string literal");
+    }
+}
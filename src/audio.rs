@@ -0,0 +1,146 @@
+use crate::error::{self, ResourceKind, Result};
+use crate::sys;
+use std::mem::ManuallyDrop;
+use std::path::Path;
+
+/// Decoded audio samples held in CPU memory, unloaded when dropped.
+#[derive(Debug)]
+pub struct OwnedWave {
+    raw: sys::Wave,
+}
+
+impl OwnedWave {
+    /// Loads and decodes a wave from a file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        error::load(
+            ResourceKind::Wave,
+            path.as_ref(),
+            |path| Self {
+                raw: unsafe { sys::LoadWave(path.as_ptr()) },
+            },
+            |wave| unsafe { sys::IsWaveValid(wave.raw) },
+        )
+    }
+
+    /// Takes ownership of a raw wave.
+    ///
+    /// # Safety
+    ///
+    /// `raw` must be a valid wave that is not unloaded anywhere else.
+    pub unsafe fn from_raw(raw: sys::Wave) -> Self {
+        Self { raw }
+    }
+
+    /// Releases ownership of the wave without unloading it.
+    pub fn into_raw(self) -> sys::Wave {
+        ManuallyDrop::new(self).raw
+    }
+
+    pub fn as_raw(&self) -> &sys::Wave {
+        &self.raw
+    }
+}
+
+impl Drop for OwnedWave {
+    fn drop(&mut self) {
+        if !self.raw.data.is_null() {
+            unsafe { sys::UnloadWave(self.raw) }
+        }
+    }
+}
+
+/// A sound fully loaded into an audio buffer, unloaded when dropped.
+///
+/// The audio device must be initialised before loading sounds.
+#[derive(Debug)]
+pub struct OwnedSound {
+    raw: sys::Sound,
+}
+
+impl OwnedSound {
+    /// Loads a sound from a file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        error::load(
+            ResourceKind::Sound,
+            path.as_ref(),
+            |path| Self {
+                raw: unsafe { sys::LoadSound(path.as_ptr()) },
+            },
+            |sound| unsafe { sys::IsSoundValid(sound.raw) },
+        )
+    }
+
+    /// Takes ownership of a raw sound.
+    ///
+    /// # Safety
+    ///
+    /// `raw` must be a valid sound that is not unloaded anywhere else.
+    pub unsafe fn from_raw(raw: sys::Sound) -> Self {
+        Self { raw }
+    }
+
+    /// Releases ownership of the sound without unloading it.
+    pub fn into_raw(self) -> sys::Sound {
+        ManuallyDrop::new(self).raw
+    }
+
+    pub fn as_raw(&self) -> &sys::Sound {
+        &self.raw
+    }
+}
+
+impl Drop for OwnedSound {
+    fn drop(&mut self) {
+        if !self.raw.stream.buffer.is_null() {
+            unsafe { sys::UnloadSound(self.raw) }
+        }
+    }
+}
+
+/// A streamed music file, unloaded when dropped.
+///
+/// The audio device must be initialised before loading music.
+#[derive(Debug)]
+pub struct OwnedMusic {
+    raw: sys::Music,
+}
+
+impl OwnedMusic {
+    /// Opens a music stream from a file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        error::load(
+            ResourceKind::Music,
+            path.as_ref(),
+            |path| Self {
+                raw: unsafe { sys::LoadMusicStream(path.as_ptr()) },
+            },
+            |music| unsafe { sys::IsMusicValid(music.raw) },
+        )
+    }
+
+    /// Takes ownership of a raw music stream.
+    ///
+    /// # Safety
+    ///
+    /// `raw` must be a valid music stream that is not unloaded anywhere else.
+    pub unsafe fn from_raw(raw: sys::Music) -> Self {
+        Self { raw }
+    }
+
+    /// Releases ownership of the music stream without unloading it.
+    pub fn into_raw(self) -> sys::Music {
+        ManuallyDrop::new(self).raw
+    }
+
+    pub fn as_raw(&self) -> &sys::Music {
+        &self.raw
+    }
+}
+
+impl Drop for OwnedMusic {
+    fn drop(&mut self) {
+        if !self.raw.ctxData.is_null() {
+            unsafe { sys::UnloadMusicStream(self.raw) }
+        }
+    }
+}
@@ -0,0 +1,223 @@
+use crate::sys;
+use crate::sys::{CameraMode, CameraProjection, Vector2, Vector3};
+use std::os::raw::c_int;
+
+mod controller;
+mod view;
+
+pub use controller::*;
+pub use view::*;
+
+/// A 2D camera.
+#[derive(Debug, Copy, Clone)]
+pub struct Camera2D {
+    /// Displacement from the target, in screen space.
+    pub offset: Vector2,
+    /// The world-space point the camera looks at.
+    pub target: Vector2,
+    /// Rotation in degrees.
+    pub rotation: f32,
+    /// Scale factor; `1.0` is unscaled.
+    pub zoom: f32,
+}
+
+impl Camera2D {
+    /// Creates an unrotated, unscaled camera.
+    pub const fn new(offset: Vector2, target: Vector2) -> Self {
+        Self {
+            offset,
+            target,
+            rotation: 0.0,
+            zoom: 1.0,
+        }
+    }
+
+    pub const fn with_offset(mut self, offset: Vector2) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    pub const fn with_target(mut self, target: Vector2) -> Self {
+        self.target = target;
+        self
+    }
+
+    pub const fn with_rotation(mut self, rotation: f32) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    pub const fn with_zoom(mut self, zoom: f32) -> Self {
+        self.zoom = zoom;
+        self
+    }
+
+    pub const fn to_raw(&self) -> sys::Camera2D {
+        sys::Camera2D {
+            offset: self.offset,
+            target: self.target,
+            rotation: self.rotation,
+            zoom: self.zoom,
+        }
+    }
+}
+
+impl Default for Camera2D {
+    fn default() -> Self {
+        Self::new(Vector2::new(0.0, 0.0), Vector2::new(0.0, 0.0))
+    }
+}
+
+impl From<sys::Camera2D> for Camera2D {
+    fn from(value: sys::Camera2D) -> Self {
+        Self {
+            offset: value.offset,
+            target: value.target,
+            rotation: value.rotation,
+            zoom: value.zoom,
+        }
+    }
+}
+
+impl From<Camera2D> for sys::Camera2D {
+    fn from(value: Camera2D) -> Self {
+        value.to_raw()
+    }
+}
+
+/// A 3D camera.
+#[derive(Debug, Copy, Clone)]
+pub struct Camera3D {
+    pub position: Vector3,
+    /// The point the camera looks at.
+    pub target: Vector3,
+    /// The camera's up vector, normally the world Y axis.
+    pub up: Vector3,
+    /// Vertical field of view in degrees for perspective cameras, or the
+    /// height of the view volume in world units for orthographic cameras.
+    pub fovy: f32,
+    pub projection: CameraProjection,
+}
+
+impl Camera3D {
+    /// Creates a perspective camera with the world Y axis as up.
+    pub const fn perspective(position: Vector3, target: Vector3, fovy: f32) -> Self {
+        Self {
+            position,
+            target,
+            up: Vector3::new(0.0, 1.0, 0.0),
+            fovy,
+            projection: CameraProjection::CAMERA_PERSPECTIVE,
+        }
+    }
+
+    /// Creates an orthographic camera with the world Y axis as up.
+    pub const fn orthographic(position: Vector3, target: Vector3, height: f32) -> Self {
+        Self {
+            position,
+            target,
+            up: Vector3::new(0.0, 1.0, 0.0),
+            fovy: height,
+            projection: CameraProjection::CAMERA_ORTHOGRAPHIC,
+        }
+    }
+
+    pub const fn with_position(mut self, position: Vector3) -> Self {
+        self.position = position;
+        self
+    }
+
+    pub const fn with_target(mut self, target: Vector3) -> Self {
+        self.target = target;
+        self
+    }
+
+    pub const fn with_up(mut self, up: Vector3) -> Self {
+        self.up = up;
+        self
+    }
+
+    pub const fn with_fovy(mut self, fovy: f32) -> Self {
+        self.fovy = fovy;
+        self
+    }
+
+    pub const fn with_projection(mut self, projection: CameraProjection) -> Self {
+        self.projection = projection;
+        self
+    }
+
+    pub const fn to_raw(&self) -> sys::Camera3D {
+        sys::Camera3D {
+            position: self.position,
+            target: self.target,
+            up: self.up,
+            fovy: self.fovy,
+            projection: self.projection as c_int,
+        }
+    }
+
+    /// The normalised direction from the position to the target.
+    pub fn forward(&self) -> Vector3 {
+        (self.target - self.position).normalize()
+    }
+
+    /// The normalised direction to the camera's right.
+    pub fn right(&self) -> Vector3 {
+        self.forward().cross(self.up).normalize()
+    }
+
+    /// Updates the camera using one of raylib's built-in, hard-wired modes.
+    pub fn update(&mut self, mode: CameraMode) {
+        let mut raw = self.to_raw();
+        unsafe { sys::UpdateCamera(&mut raw, mode as c_int) };
+        *self = raw.into();
+    }
+
+    /// Moves and rotates the camera through `UpdateCameraPro`.
+    ///
+    /// `movement` is forward, right, and up in world units; `rotation` is
+    /// yaw, pitch, and roll in degrees; `zoom` moves towards the target.
+    /// Forward and right movement stay in the world's horizontal plane.
+    pub fn update_pro(&mut self, movement: Vector3, rotation: Vector3, zoom: f32) {
+        let mut raw = self.to_raw();
+        unsafe { sys::UpdateCameraPro(&mut raw, movement, rotation, zoom) };
+        *self = raw.into();
+    }
+}
+
+impl Default for Camera3D {
+    fn default() -> Self {
+        Self::perspective(
+            Vector3::new(0.0, 10.0, 10.0),
+            Vector3::new(0.0, 0.0, 0.0),
+            45.0,
+        )
+    }
+}
+
+impl From<sys::Camera3D> for Camera3D {
+    /// Converts a raw camera; any projection other than orthographic is
+    /// treated as perspective.
+    fn from(value: sys::Camera3D) -> Self {
+        let projection = if value.projection == CameraProjection::CAMERA_ORTHOGRAPHIC as c_int {
+            CameraProjection::CAMERA_ORTHOGRAPHIC
+        } else {
+            CameraProjection::CAMERA_PERSPECTIVE
+        };
+
+        Self {
+            position: value.position,
+            target: value.target,
+            up: value.up,
+            fovy: value.fovy,
+            projection,
+        }
+    }
+}
+
+impl From<Camera3D> for sys::Camera3D {
+    fn from(value: Camera3D) -> Self {
+        value.to_raw()
+    }
+}
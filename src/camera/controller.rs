@@ -0,0 +1,365 @@
+use super::Camera3D;
+use crate::sys;
+use crate::sys::{GamepadAxis, KeyboardKey, Vector2, Vector3};
+use std::os::raw::c_int;
+
+/// One frame of camera input, independent of where it came from.
+///
+/// Controllers only ever see this struct, so input can be read from the
+/// keyboard and mouse, a gamepad, a replay, or a network peer alike.
+#[derive(Debug, Copy, Clone)]
+pub struct CameraInput {
+    /// Forward, right, and up movement, each nominally in `[-1, 1]`.
+    pub movement: Vector3,
+    /// Yaw and pitch deltas in input units (e.g. mouse pixels).
+    pub look: Vector2,
+    /// Zoom delta; positive values zoom in.
+    pub zoom: f32,
+}
+
+impl CameraInput {
+    pub const NONE: Self = Self {
+        movement: Vector3::new(0.0, 0.0, 0.0),
+        look: Vector2::new(0.0, 0.0),
+        zoom: 0.0,
+    };
+
+    /// Reads WASD, space/left-control, the mouse delta, and the wheel.
+    pub fn from_keyboard_mouse() -> Self {
+        let axis = |pos: KeyboardKey, neg: KeyboardKey| unsafe {
+            sys::IsKeyDown(pos as c_int) as i32 as f32 - sys::IsKeyDown(neg as c_int) as i32 as f32
+        };
+
+        Self {
+            movement: Vector3::new(
+                axis(KeyboardKey::KEY_W, KeyboardKey::KEY_S),
+                axis(KeyboardKey::KEY_D, KeyboardKey::KEY_A),
+                axis(KeyboardKey::KEY_SPACE, KeyboardKey::KEY_LEFT_CONTROL),
+            ),
+            look: unsafe { sys::GetMouseDelta() },
+            zoom: unsafe { sys::GetMouseWheelMove() },
+        }
+    }
+
+    /// Reads the left stick for movement, the right stick for looking, and
+    /// the triggers for vertical movement.
+    ///
+    /// Stick values are scaled by `look_scale` so they are comparable to
+    /// mouse deltas.
+    pub fn from_gamepad(gamepad: i32, look_scale: f32) -> Self {
+        let axis =
+            |axis: GamepadAxis| unsafe { sys::GetGamepadAxisMovement(gamepad, axis as c_int) };
+
+        Self {
+            movement: Vector3::new(
+                -axis(GamepadAxis::GAMEPAD_AXIS_LEFT_Y),
+                axis(GamepadAxis::GAMEPAD_AXIS_LEFT_X),
+                (axis(GamepadAxis::GAMEPAD_AXIS_RIGHT_TRIGGER)
+                    - axis(GamepadAxis::GAMEPAD_AXIS_LEFT_TRIGGER))
+                    / 2.0,
+            ),
+            look: Vector2::new(
+                axis(GamepadAxis::GAMEPAD_AXIS_RIGHT_X) * look_scale,
+                axis(GamepadAxis::GAMEPAD_AXIS_RIGHT_Y) * look_scale,
+            ),
+            zoom: 0.0,
+        }
+    }
+
+    /// Sums two inputs, e.g. to accept the keyboard and a gamepad at once.
+    pub fn combine(self, other: Self) -> Self {
+        Self {
+            movement: self.movement + other.movement,
+            look: self.look + other.look,
+            zoom: self.zoom + other.zoom,
+        }
+    }
+}
+
+impl Default for CameraInput {
+    fn default() -> Self {
+        Self::NONE
+    }
+}
+
+/// Tuning shared by every controller.
+#[derive(Debug, Copy, Clone)]
+pub struct ControllerSettings {
+    /// Movement speed in world units per second.
+    pub move_speed: f32,
+    /// Degrees of rotation per unit of look input.
+    pub look_sensitivity: f32,
+    /// World units of zoom per unit of zoom input.
+    pub zoom_speed: f32,
+    /// Time constant of the input smoothing in seconds; `0.0` disables it.
+    pub smoothing: f32,
+    /// Inverts vertical look input.
+    pub invert_y: bool,
+}
+
+impl Default for ControllerSettings {
+    fn default() -> Self {
+        Self {
+            move_speed: 5.4,
+            look_sensitivity: 0.003_f32.to_degrees(),
+            zoom_speed: 1.0,
+            smoothing: 0.0,
+            invert_y: false,
+        }
+    }
+}
+
+/// Updates a camera from a frame of input.
+pub trait CameraController {
+    fn update(&mut self, camera: &mut Camera3D, input: CameraInput, dt: f32);
+}
+
+/// Exponentially smooths input over time.
+#[derive(Debug, Copy, Clone, Default)]
+struct Smoother {
+    value: CameraInput,
+}
+
+impl Smoother {
+    fn apply(&mut self, input: CameraInput, settings: &ControllerSettings, dt: f32) -> CameraInput {
+        let t = if settings.smoothing > 0.0 {
+            1.0 - (-dt / settings.smoothing).exp()
+        } else {
+            1.0
+        };
+
+        let lerp = |a: f32, b: f32| a + (b - a) * t;
+        let v = &self.value;
+
+        self.value = CameraInput {
+            movement: Vector3::new(
+                lerp(v.movement.x, input.movement.x),
+                lerp(v.movement.y, input.movement.y),
+                lerp(v.movement.z, input.movement.z),
+            ),
+            look: Vector2::new(lerp(v.look.x, input.look.x), lerp(v.look.y, input.look.y)),
+            zoom: lerp(v.zoom, input.zoom),
+        };
+
+        self.value
+    }
+}
+
+fn look_degrees(input: &CameraInput, settings: &ControllerSettings) -> (f32, f32) {
+    let pitch = if settings.invert_y {
+        -input.look.y
+    } else {
+        input.look.y
+    };
+
+    (
+        input.look.x * settings.look_sensitivity,
+        pitch * settings.look_sensitivity,
+    )
+}
+
+/// Flies freely in the direction the camera faces, including vertically.
+#[derive(Debug, Clone, Default)]
+pub struct FreeFlyController {
+    pub settings: ControllerSettings,
+    smoother: Smoother,
+}
+
+impl FreeFlyController {
+    pub fn new(settings: ControllerSettings) -> Self {
+        Self {
+            settings,
+            smoother: Smoother::default(),
+        }
+    }
+}
+
+impl CameraController for FreeFlyController {
+    fn update(&mut self, camera: &mut Camera3D, input: CameraInput, dt: f32) {
+        let input = self.smoother.apply(input, &self.settings, dt);
+        let (yaw, pitch) = look_degrees(&input, &self.settings);
+        let distance = input.movement.x * self.settings.move_speed * dt;
+
+        // UpdateCameraPro keeps forward movement in the horizontal plane, so
+        // split it into that plane and the up axis to fly along the view.
+        let forward = camera.forward();
+        let up = camera.up.normalize();
+        let along_up = forward.dot(up);
+        let planar = (forward - up * along_up).length();
+
+        camera.update_pro(
+            Vector3::new(
+                distance * planar,
+                input.movement.y * self.settings.move_speed * dt,
+                distance * along_up + input.movement.z * self.settings.move_speed * dt,
+            ),
+            Vector3::new(yaw, pitch, 0.0),
+            -input.zoom * self.settings.zoom_speed,
+        );
+    }
+}
+
+/// Walks in the horizontal plane and looks around, like raylib's
+/// `CAMERA_FIRST_PERSON` mode.
+#[derive(Debug, Clone, Default)]
+pub struct FirstPersonController {
+    pub settings: ControllerSettings,
+    smoother: Smoother,
+}
+
+impl FirstPersonController {
+    pub fn new(settings: ControllerSettings) -> Self {
+        Self {
+            settings,
+            smoother: Smoother::default(),
+        }
+    }
+}
+
+impl CameraController for FirstPersonController {
+    fn update(&mut self, camera: &mut Camera3D, input: CameraInput, dt: f32) {
+        let input = self.smoother.apply(input, &self.settings, dt);
+        let (yaw, pitch) = look_degrees(&input, &self.settings);
+        let speed = self.settings.move_speed * dt;
+
+        camera.update_pro(
+            Vector3::new(
+                input.movement.x * speed,
+                input.movement.y * speed,
+                input.movement.z * speed,
+            ),
+            Vector3::new(yaw, pitch, 0.0),
+            0.0,
+        );
+    }
+}
+
+/// Maximum pitch away from the horizon for orbiting controllers, in degrees.
+const MAX_ORBIT_PITCH: f32 = 89.0;
+
+/// Rotates the camera's position around its target by yaw and pitch degrees
+/// and moves it `zoom` units closer, keeping at least `min_distance`.
+///
+/// Orbiting assumes the world Y axis is up.
+fn orbit(camera: &mut Camera3D, yaw: f32, pitch: f32, zoom: f32, min_distance: f32) {
+    let offset = camera.position - camera.target;
+    let distance = (offset.length() - zoom).max(min_distance);
+
+    let current_yaw = offset.x.atan2(offset.z);
+    let current_pitch = (offset.y / offset.length().max(f32::EPSILON))
+        .clamp(-1.0, 1.0)
+        .asin();
+
+    let yaw = current_yaw - yaw.to_radians();
+    let pitch = (current_pitch + pitch.to_radians())
+        .clamp(-MAX_ORBIT_PITCH.to_radians(), MAX_ORBIT_PITCH.to_radians());
+
+    camera.position = camera.target
+        + Vector3::new(
+            distance * pitch.cos() * yaw.sin(),
+            distance * pitch.sin(),
+            distance * pitch.cos() * yaw.cos(),
+        );
+}
+
+/// Orbits around the camera's target, optionally spinning on its own.
+#[derive(Debug, Clone)]
+pub struct OrbitalController {
+    pub settings: ControllerSettings,
+    /// Automatic rotation in degrees per second.
+    pub auto_rotate: f32,
+    /// The closest the camera may get to its target.
+    pub min_distance: f32,
+    smoother: Smoother,
+}
+
+impl OrbitalController {
+    pub fn new(settings: ControllerSettings) -> Self {
+        Self {
+            settings,
+            auto_rotate: 0.0,
+            min_distance: 0.1,
+            smoother: Smoother::default(),
+        }
+    }
+
+    pub fn with_auto_rotate(mut self, degrees_per_second: f32) -> Self {
+        self.auto_rotate = degrees_per_second;
+        self
+    }
+}
+
+impl Default for OrbitalController {
+    fn default() -> Self {
+        Self::new(ControllerSettings::default())
+    }
+}
+
+impl CameraController for OrbitalController {
+    fn update(&mut self, camera: &mut Camera3D, input: CameraInput, dt: f32) {
+        let input = self.smoother.apply(input, &self.settings, dt);
+        let (yaw, pitch) = look_degrees(&input, &self.settings);
+
+        orbit(
+            camera,
+            yaw + self.auto_rotate * dt,
+            pitch,
+            input.zoom * self.settings.zoom_speed,
+            self.min_distance,
+        );
+    }
+}
+
+/// Follows a target that moves in the horizontal plane relative to the
+/// camera's heading, orbiting around it with look input.
+#[derive(Debug, Clone)]
+pub struct ThirdPersonController {
+    pub settings: ControllerSettings,
+    /// The closest the camera may get to its target.
+    pub min_distance: f32,
+    smoother: Smoother,
+}
+
+impl ThirdPersonController {
+    pub fn new(settings: ControllerSettings) -> Self {
+        Self {
+            settings,
+            min_distance: 1.0,
+            smoother: Smoother::default(),
+        }
+    }
+}
+
+impl Default for ThirdPersonController {
+    fn default() -> Self {
+        Self::new(ControllerSettings::default())
+    }
+}
+
+impl CameraController for ThirdPersonController {
+    fn update(&mut self, camera: &mut Camera3D, input: CameraInput, dt: f32) {
+        let input = self.smoother.apply(input, &self.settings, dt);
+        let (yaw, pitch) = look_degrees(&input, &self.settings);
+        let speed = self.settings.move_speed * dt;
+
+        let up = camera.up.normalize();
+        let forward = camera.forward();
+        let forward = (forward - up * forward.dot(up)).normalize();
+        let right = forward.cross(up).normalize();
+
+        let step = forward * (input.movement.x * speed)
+            + right * (input.movement.y * speed)
+            + up * (input.movement.z * speed);
+
+        camera.position += step;
+        camera.target += step;
+
+        orbit(
+            camera,
+            yaw,
+            pitch,
+            input.zoom * self.settings.zoom_speed,
+            self.min_distance,
+        );
+    }
+}
@@ -0,0 +1,509 @@
+use super::{Camera2D, Camera3D};
+use crate::sys;
+use crate::sys::{BoundingBox, CameraProjection, Matrix, Ray, Rectangle, Vector2, Vector3};
+
+/// A render target and where it is presented on screen.
+///
+/// Conversions that take a viewport treat screen positions as being on the
+/// window and map them into the target's own `width` x `height` pixel
+/// space, so picking and culling work when a scene is rendered to a render
+/// texture that is then drawn scaled or letterboxed.
+#[derive(Debug, Copy, Clone)]
+pub struct Viewport {
+    pub width: i32,
+    pub height: i32,
+    /// Where the target is drawn on screen.
+    pub dest: Rectangle,
+}
+
+impl Viewport {
+    /// A target drawn unscaled at the window's origin.
+    pub fn new(width: i32, height: i32) -> Self {
+        Self {
+            width,
+            height,
+            dest: Rectangle {
+                x: 0.0,
+                y: 0.0,
+                width: width as f32,
+                height: height as f32,
+            },
+        }
+    }
+
+    /// The whole window.
+    pub fn screen() -> Self {
+        unsafe { Self::new(sys::GetScreenWidth(), sys::GetScreenHeight()) }
+    }
+
+    pub fn with_dest(mut self, dest: Rectangle) -> Self {
+        self.dest = dest;
+        self
+    }
+
+    pub fn aspect(&self) -> f32 {
+        self.width as f32 / self.height as f32
+    }
+
+    /// Maps a window position into the target's pixel space.
+    pub fn to_local(&self, screen: Vector2) -> Vector2 {
+        Vector2::new(
+            (screen.x - self.dest.x) * self.width as f32 / self.dest.width,
+            (screen.y - self.dest.y) * self.height as f32 / self.dest.height,
+        )
+    }
+
+    /// Maps a position in the target's pixel space onto the window.
+    pub fn to_screen(&self, local: Vector2) -> Vector2 {
+        Vector2::new(
+            self.dest.x + local.x * self.dest.width / self.width as f32,
+            self.dest.y + local.y * self.dest.height / self.height as f32,
+        )
+    }
+}
+
+impl Camera2D {
+    /// Converts a screen position into world space.
+    pub fn screen_to_world(&self, position: Vector2) -> Vector2 {
+        unsafe { sys::GetScreenToWorld2D(position, self.to_raw()) }
+    }
+
+    /// Converts a world position into screen space.
+    pub fn world_to_screen(&self, position: Vector2) -> Vector2 {
+        unsafe { sys::GetWorldToScreen2D(position, self.to_raw()) }
+    }
+
+    /// Converts a window position into world space for a camera rendering
+    /// into `viewport`.
+    pub fn screen_to_world_in(&self, position: Vector2, viewport: &Viewport) -> Vector2 {
+        self.screen_to_world(viewport.to_local(position))
+    }
+
+    /// Converts a world position into a window position for a camera
+    /// rendering into `viewport`.
+    pub fn world_to_screen_in(&self, position: Vector2, viewport: &Viewport) -> Vector2 {
+        viewport.to_screen(self.world_to_screen(position))
+    }
+
+    /// The camera's transformation matrix.
+    pub fn matrix(&self) -> Matrix {
+        unsafe { sys::GetCameraMatrix2D(self.to_raw()) }
+    }
+
+    /// The world-space bounding rectangle of everything visible on screen.
+    pub fn visible_rect(&self) -> Rectangle {
+        let viewport = Viewport::screen();
+        self.visible_rect_in(&viewport)
+    }
+
+    /// The world-space bounding rectangle of everything visible in
+    /// `viewport`'s target. Rotated cameras produce the enclosing
+    /// axis-aligned rectangle.
+    pub fn visible_rect_in(&self, viewport: &Viewport) -> Rectangle {
+        let (w, h) = (viewport.width as f32, viewport.height as f32);
+        let corners = [
+            Vector2::new(0.0, 0.0),
+            Vector2::new(w, 0.0),
+            Vector2::new(0.0, h),
+            Vector2::new(w, h),
+        ]
+        .map(|corner| self.screen_to_world(corner));
+
+        let (mut min, mut max) = (corners[0], corners[0]);
+
+        for corner in &corners[1..] {
+            min = min.min(*corner);
+            max = max.max(*corner);
+        }
+
+        Rectangle {
+            x: min.x,
+            y: min.y,
+            width: max.x - min.x,
+            height: max.y - min.y,
+        }
+    }
+}
+
+impl Camera3D {
+    /// Casts a ray from a screen position into the world.
+    pub fn screen_to_world_ray(&self, position: Vector2) -> Ray {
+        unsafe { sys::GetScreenToWorldRay(position, self.to_raw()) }
+    }
+
+    /// Casts a ray from a window position into the world for a camera
+    /// rendering into `viewport`.
+    pub fn screen_to_world_ray_in(&self, position: Vector2, viewport: &Viewport) -> Ray {
+        let local = viewport.to_local(position);
+        unsafe { sys::GetScreenToWorldRayEx(local, self.to_raw(), viewport.width, viewport.height) }
+    }
+
+    /// Projects a world position onto the screen.
+    pub fn world_to_screen(&self, position: Vector3) -> Vector2 {
+        unsafe { sys::GetWorldToScreen(position, self.to_raw()) }
+    }
+
+    /// Projects a world position onto the window for a camera rendering into
+    /// `viewport`.
+    pub fn world_to_screen_in(&self, position: Vector3, viewport: &Viewport) -> Vector2 {
+        let local = unsafe {
+            sys::GetWorldToScreenEx(position, self.to_raw(), viewport.width, viewport.height)
+        };
+
+        viewport.to_screen(local)
+    }
+
+    /// The camera's view matrix.
+    pub fn view_matrix(&self) -> Matrix {
+        unsafe { sys::GetCameraMatrix(self.to_raw()) }
+    }
+
+    /// The projection matrix `BeginMode3D` uses for the given aspect ratio.
+    pub fn projection_matrix(&self, aspect: f32) -> Matrix {
+        let near = sys::RL_CULL_DISTANCE_NEAR as f32;
+        let far = sys::RL_CULL_DISTANCE_FAR as f32;
+
+        let top = match self.projection {
+            CameraProjection::CAMERA_PERSPECTIVE => near * (self.fovy.to_radians() * 0.5).tan(),
+            CameraProjection::CAMERA_ORTHOGRAPHIC => self.fovy * 0.5,
+        };
+        let right = top * aspect;
+
+        let (rl, tb, fne) = (2.0 * right, 2.0 * top, far - near);
+        let mut m = [[0.0; 4]; 4];
+
+        match self.projection {
+            CameraProjection::CAMERA_PERSPECTIVE => {
+                m[0][0] = 2.0 * near / rl;
+                m[1][1] = 2.0 * near / tb;
+                m[2][2] = -(far + near) / fne;
+                m[2][3] = -(2.0 * far * near) / fne;
+                m[3][2] = -1.0;
+            }
+            CameraProjection::CAMERA_ORTHOGRAPHIC => {
+                m[0][0] = 2.0 / rl;
+                m[1][1] = 2.0 / tb;
+                m[2][2] = -2.0 / fne;
+                m[2][3] = -(far + near) / fne;
+                m[3][3] = 1.0;
+            }
+        }
+
+        from_rows(m)
+    }
+
+    /// The camera's view frustum on screen.
+    pub fn frustum(&self) -> Frustum {
+        self.frustum_in(&Viewport::screen())
+    }
+
+    /// The camera's view frustum when rendering into `viewport`.
+    pub fn frustum_in(&self, viewport: &Viewport) -> Frustum {
+        let view = rows(&self.view_matrix());
+        let projection = rows(&self.projection_matrix(viewport.aspect()));
+        Frustum::from_rows(&mul(&projection, &view))
+    }
+}
+
+/// A plane in the form `dot(normal, p) + distance = 0`.
+#[derive(Debug, Copy, Clone)]
+pub struct Plane {
+    pub normal: Vector3,
+    pub distance: f32,
+}
+
+impl Plane {
+    /// The signed distance from the plane to `point`; positive values lie on
+    /// the side the normal points to.
+    pub fn signed_distance(&self, point: Vector3) -> f32 {
+        self.normal.dot(point) + self.distance
+    }
+
+    fn normalized(self) -> Self {
+        let len = self.normal.length();
+
+        Self {
+            normal: self.normal / len,
+            distance: self.distance / len,
+        }
+    }
+}
+
+/// The six planes bounding a camera's visible volume, with normals pointing
+/// inwards.
+#[derive(Debug, Copy, Clone)]
+pub struct Frustum {
+    pub left: Plane,
+    pub right: Plane,
+    pub bottom: Plane,
+    pub top: Plane,
+    pub near: Plane,
+    pub far: Plane,
+}
+
+impl Frustum {
+    /// Extracts the planes from a row-major view-projection matrix.
+    fn from_rows(m: &[[f32; 4]; 4]) -> Self {
+        let plane = |sign: f32, row: usize| {
+            Plane {
+                normal: Vector3::new(
+                    m[3][0] + sign * m[row][0],
+                    m[3][1] + sign * m[row][1],
+                    m[3][2] + sign * m[row][2],
+                ),
+                distance: m[3][3] + sign * m[row][3],
+            }
+            .normalized()
+        };
+
+        Self {
+            left: plane(1.0, 0),
+            right: plane(-1.0, 0),
+            bottom: plane(1.0, 1),
+            top: plane(-1.0, 1),
+            near: plane(1.0, 2),
+            far: plane(-1.0, 2),
+        }
+    }
+
+    pub fn planes(&self) -> [Plane; 6] {
+        [
+            self.left,
+            self.right,
+            self.bottom,
+            self.top,
+            self.near,
+            self.far,
+        ]
+    }
+
+    pub fn contains_point(&self, point: Vector3) -> bool {
+        self.planes()
+            .iter()
+            .all(|plane| plane.signed_distance(point) >= 0.0)
+    }
+
+    /// Returns `false` only if the sphere lies entirely outside the frustum.
+    pub fn intersects_sphere(&self, center: Vector3, radius: f32) -> bool {
+        self.planes()
+            .iter()
+            .all(|plane| plane.signed_distance(center) >= -radius)
+    }
+
+    /// Returns `false` only if the box lies entirely outside the frustum.
+    pub fn intersects_box(&self, bounds: &BoundingBox) -> bool {
+        self.planes().iter().all(|plane| {
+            // Test the corner furthest along the plane's normal.
+            let corner = Vector3::new(
+                if plane.normal.x >= 0.0 {
+                    bounds.max.x
+                } else {
+                    bounds.min.x
+                },
+                if plane.normal.y >= 0.0 {
+                    bounds.max.y
+                } else {
+                    bounds.min.y
+                },
+                if plane.normal.z >= 0.0 {
+                    bounds.max.z
+                } else {
+                    bounds.min.z
+                },
+            );
+
+            plane.signed_distance(corner) >= 0.0
+        })
+    }
+}
+
+/// Converts a raylib matrix into row-major form.
+fn rows(m: &Matrix) -> [[f32; 4]; 4] {
+    [
+        [m.m0, m.m4, m.m8, m.m12],
+        [m.m1, m.m5, m.m9, m.m13],
+        [m.m2, m.m6, m.m10, m.m14],
+        [m.m3, m.m7, m.m11, m.m15],
+    ]
+}
+
+fn from_rows(r: [[f32; 4]; 4]) -> Matrix {
+    Matrix {
+        m0: r[0][0],
+        m4: r[0][1],
+        m8: r[0][2],
+        m12: r[0][3],
+        m1: r[1][0],
+        m5: r[1][1],
+        m9: r[1][2],
+        m13: r[1][3],
+        m2: r[2][0],
+        m6: r[2][1],
+        m10: r[2][2],
+        m14: r[2][3],
+        m3: r[3][0],
+        m7: r[3][1],
+        m11: r[3][2],
+        m15: r[3][3],
+    }
+}
+
+fn mul(a: &[[f32; 4]; 4], b: &[[f32; 4]; 4]) -> [[f32; 4]; 4] {
+    let mut out = [[0.0; 4]; 4];
+
+    for (i, row) in out.iter_mut().enumerate() {
+        for (j, cell) in row.iter_mut().enumerate() {
+            *cell = (0..4).map(|k| a[i][k] * b[k][j]).sum();
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const IDENTITY: [[f32; 4]; 4] = [
+        [1.0, 0.0, 0.0, 0.0],
+        [0.0, 1.0, 0.0, 0.0],
+        [0.0, 0.0, 1.0, 0.0],
+        [0.0, 0.0, 0.0, 1.0],
+    ];
+
+    fn assert_close(actual: Vector2, expected: Vector2) {
+        assert!(
+            (actual - expected).length() < 1e-4,
+            "{actual:?} != {expected:?}"
+        );
+    }
+
+    fn assert_matrix_close(actual: &Matrix, expected: &Matrix) {
+        let (actual, expected) = (rows(actual), rows(expected));
+
+        for (a, e) in actual.iter().flatten().zip(expected.iter().flatten()) {
+            assert!(
+                (a - e).abs() <= 1e-5 * e.abs().max(1.0),
+                "{actual:?} != {expected:?}"
+            );
+        }
+    }
+
+    /// A camera at the origin looking down -Z, whose view matrix is the
+    /// identity.
+    fn frustum(camera: &Camera3D, aspect: f32) -> Frustum {
+        Frustum::from_rows(&mul(&rows(&camera.projection_matrix(aspect)), &IDENTITY))
+    }
+
+    #[test]
+    fn viewport_round_trips() {
+        let viewport = Viewport::new(320, 180).with_dest(Rectangle {
+            x: 40.0,
+            y: 30.0,
+            width: 960.0,
+            height: 540.0,
+        });
+
+        assert_close(viewport.to_local(Vector2::new(40.0, 30.0)), Vector2::ZERO);
+        assert_close(
+            viewport.to_local(Vector2::new(1000.0, 570.0)),
+            Vector2::new(320.0, 180.0),
+        );
+        assert_close(
+            viewport.to_screen(Vector2::new(160.0, 90.0)),
+            Vector2::new(520.0, 300.0),
+        );
+
+        for point in [
+            Vector2::new(0.0, 0.0),
+            Vector2::new(12.5, 99.0),
+            Vector2::new(-10.0, 400.0),
+        ] {
+            assert_close(viewport.to_local(viewport.to_screen(point)), point);
+            assert_close(viewport.to_screen(viewport.to_local(point)), point);
+        }
+    }
+
+    #[test]
+    fn unscaled_viewport_is_the_identity() {
+        let viewport = Viewport::new(800, 600);
+        let point = Vector2::new(123.0, 456.0);
+
+        assert_close(viewport.to_local(point), point);
+        assert_close(viewport.to_screen(point), point);
+        assert_eq!(viewport.aspect(), 800.0 / 600.0);
+    }
+
+    #[test]
+    fn perspective_matches_raymath() {
+        let near = sys::RL_CULL_DISTANCE_NEAR;
+        let far = sys::RL_CULL_DISTANCE_FAR;
+
+        for (fovy, aspect) in [(45.0f32, 16.0f32 / 9.0), (90.0, 1.0), (60.0, 0.5)] {
+            let camera = Camera3D::perspective(Vector3::ZERO, Vector3::new(0.0, 0.0, -1.0), fovy);
+            let expected = unsafe {
+                sys::MatrixPerspective(fovy.to_radians() as f64, aspect as f64, near, far)
+            };
+
+            assert_matrix_close(&camera.projection_matrix(aspect), &expected);
+        }
+    }
+
+    #[test]
+    fn orthographic_matches_raymath() {
+        let near = sys::RL_CULL_DISTANCE_NEAR;
+        let far = sys::RL_CULL_DISTANCE_FAR;
+
+        for (height, aspect) in [(10.0f32, 16.0f32 / 9.0), (2.0, 1.0), (100.0, 0.5)] {
+            let camera =
+                Camera3D::orthographic(Vector3::ZERO, Vector3::new(0.0, 0.0, -1.0), height);
+            let top = height as f64 / 2.0;
+            let right = top * aspect as f64;
+            let expected = unsafe { sys::MatrixOrtho(-right, right, -top, top, near, far) };
+
+            assert_matrix_close(&camera.projection_matrix(aspect), &expected);
+        }
+    }
+
+    #[test]
+    fn perspective_frustum_contains_points_in_front() {
+        let camera = Camera3D::perspective(Vector3::ZERO, Vector3::new(0.0, 0.0, -1.0), 60.0);
+        let frustum = frustum(&camera, 1.0);
+
+        assert!(frustum.contains_point(Vector3::new(0.0, 0.0, -10.0)));
+        assert!(frustum.contains_point(Vector3::new(1.0, -1.0, -10.0)));
+        assert!(!frustum.contains_point(Vector3::new(0.0, 0.0, 10.0)));
+        assert!(!frustum.contains_point(Vector3::new(0.0, 0.0, -2000.0)));
+        assert!(!frustum.contains_point(Vector3::new(100.0, 0.0, -10.0)));
+        assert!(!frustum.contains_point(Vector3::new(0.0, -100.0, -10.0)));
+
+        assert!(frustum.intersects_sphere(Vector3::new(0.0, 0.0, 1.0), 2.0));
+        assert!(!frustum.intersects_sphere(Vector3::new(0.0, 0.0, 10.0), 1.0));
+
+        let behind = BoundingBox {
+            min: Vector3::new(-1.0, -1.0, 5.0),
+            max: Vector3::new(1.0, 1.0, 6.0),
+        };
+        let straddling = BoundingBox {
+            min: Vector3::new(-1.0, -1.0, -5.0),
+            max: Vector3::new(1.0, 1.0, 5.0),
+        };
+        assert!(!frustum.intersects_box(&behind));
+        assert!(frustum.intersects_box(&straddling));
+    }
+
+    #[test]
+    fn orthographic_frustum_is_a_box() {
+        let camera = Camera3D::orthographic(Vector3::ZERO, Vector3::new(0.0, 0.0, -1.0), 10.0);
+        let frustum = frustum(&camera, 2.0);
+
+        assert!(frustum.contains_point(Vector3::new(9.9, 4.9, -500.0)));
+        assert!(!frustum.contains_point(Vector3::new(10.1, 0.0, -1.0)));
+        assert!(!frustum.contains_point(Vector3::new(0.0, 5.1, -1.0)));
+        assert!(!frustum.contains_point(Vector3::new(0.0, 0.0, 1.0)));
+
+        let plane = frustum.left;
+        assert!((plane.normal.length() - 1.0).abs() < 1e-5);
+        assert!((plane.signed_distance(Vector3::new(-9.0, 0.0, -1.0)) - 1.0).abs() < 1e-4);
+    }
+}
@@ -0,0 +1,553 @@
+use crate::error::{Error, Result};
+use crate::sys::{Color, Vector3, Vector4};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+
+mod oklab;
+mod palette;
+
+pub use oklab::*;
+pub use palette::*;
+
+impl Color {
+    pub const fn new(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Self { r, g, b, a }
+    }
+
+    /// An opaque color.
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Self::new(r, g, b, 255)
+    }
+
+    /// Unpacks a color from `0xRRGGBBAA`, like `GetColor`.
+    pub const fn from_u32(hex: u32) -> Self {
+        Self::new(
+            (hex >> 24) as u8,
+            (hex >> 16) as u8,
+            (hex >> 8) as u8,
+            hex as u8,
+        )
+    }
+
+    /// Packs the color into `0xRRGGBBAA`, like `ColorToInt`.
+    pub const fn to_u32(self) -> u32 {
+        (self.r as u32) << 24 | (self.g as u32) << 16 | (self.b as u32) << 8 | self.a as u32
+    }
+
+    /// Parses a hex color such as `"#RRGGBBAA"`, with or without the `#`.
+    ///
+    /// Also accepts `RRGGBB`, and the shorthand `RGB` and `RGBA` forms, in
+    /// which each digit is doubled. Colors without alpha are opaque.
+    pub fn from_hex(hex: &str) -> Result<Self> {
+        let invalid = || Error::ParseColor {
+            input: hex.to_owned(),
+        };
+
+        let digits = hex.strip_prefix('#').unwrap_or(hex);
+
+        if !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(invalid());
+        }
+
+        let value = u32::from_str_radix(digits, 16).map_err(|_| invalid())?;
+        let nibble = |shift: u32| ((value >> shift) & 0xF) as u8 * 0x11;
+
+        match digits.len() {
+            3 => Ok(Self::rgb(nibble(8), nibble(4), nibble(0))),
+            4 => Ok(Self::new(nibble(12), nibble(8), nibble(4), nibble(0))),
+            6 => Ok(Self::from_u32(value << 8 | 0xFF)),
+            8 => Ok(Self::from_u32(value)),
+            _ => Err(invalid()),
+        }
+    }
+
+    /// Formats the color as `"#RRGGBBAA"`.
+    pub fn to_hex(self) -> String {
+        format!("#{:08X}", self.to_u32())
+    }
+
+    /// Each channel scaled to `0.0..=1.0`, like `ColorNormalize`.
+    pub fn normalize(self) -> Vector4 {
+        Vector4 {
+            x: f32::from(self.r) / 255.0,
+            y: f32::from(self.g) / 255.0,
+            z: f32::from(self.b) / 255.0,
+            w: f32::from(self.a) / 255.0,
+        }
+    }
+
+    /// The inverse of [`Self::normalize`], like `ColorFromNormalized`.
+    /// Channels outside `0.0..=1.0` are clamped.
+    pub fn from_normalized(normalized: Vector4) -> Self {
+        let channel = |c: f32| (c * 255.0) as u8;
+
+        Self::new(
+            channel(normalized.x),
+            channel(normalized.y),
+            channel(normalized.z),
+            channel(normalized.w),
+        )
+    }
+
+    /// Converts to hue (in degrees), saturation, and value, like
+    /// `ColorToHSV`. Alpha is ignored.
+    pub fn to_hsv(self) -> Vector3 {
+        let (hue, min, max) = self.hue();
+        let delta = max - min;
+
+        Vector3 {
+            x: hue,
+            y: if delta < 0.00001 { 0.0 } else { delta / max },
+            z: max,
+        }
+    }
+
+    /// Creates an opaque color from hue (in degrees), saturation, and value,
+    /// like `ColorFromHSV`.
+    pub fn from_hsv(hue: f32, saturation: f32, value: f32) -> Self {
+        let channel = |n: f32| {
+            let k = (n + hue / 60.0) % 6.0;
+            let k = k.min(4.0 - k).clamp(0.0, 1.0);
+            ((value - value * saturation * k) * 255.0) as u8
+        };
+
+        Self::rgb(channel(5.0), channel(3.0), channel(1.0))
+    }
+
+    /// Converts to hue (in degrees), saturation, and lightness. Alpha is
+    /// ignored.
+    pub fn to_hsl(self) -> Vector3 {
+        let (hue, min, max) = self.hue();
+        let delta = max - min;
+        let lightness = (max + min) / 2.0;
+
+        let saturation = if delta < 0.00001 {
+            0.0
+        } else {
+            delta / (1.0 - (2.0 * lightness - 1.0).abs())
+        };
+
+        Vector3 {
+            x: hue,
+            y: saturation,
+            z: lightness,
+        }
+    }
+
+    /// Creates an opaque color from hue (in degrees), saturation, and
+    /// lightness.
+    pub fn from_hsl(hue: f32, saturation: f32, lightness: f32) -> Self {
+        let a = saturation * lightness.min(1.0 - lightness);
+        let channel = |n: f32| {
+            let k = (n + hue / 30.0).rem_euclid(12.0);
+            let c = lightness - a * (k - 3.0).min(9.0 - k).clamp(-1.0, 1.0);
+            (c * 255.0).round() as u8
+        };
+
+        Self::rgb(channel(0.0), channel(8.0), channel(4.0))
+    }
+
+    /// Converts from sRGB to linear RGB, with each channel in `0.0..=1.0`.
+    /// Alpha is already linear and is only scaled.
+    pub fn to_linear(self) -> Vector4 {
+        let n = self.normalize();
+
+        Vector4 {
+            x: srgb_to_linear(n.x),
+            y: srgb_to_linear(n.y),
+            z: srgb_to_linear(n.z),
+            w: n.w,
+        }
+    }
+
+    /// The inverse of [`Self::to_linear`]. Channels outside `0.0..=1.0` are
+    /// clamped.
+    pub fn from_linear(linear: Vector4) -> Self {
+        let channel = |c: f32| (c.clamp(0.0, 1.0) * 255.0).round() as u8;
+
+        Self::new(
+            channel(linear_to_srgb(linear.x)),
+            channel(linear_to_srgb(linear.y)),
+            channel(linear_to_srgb(linear.z)),
+            channel(linear.w),
+        )
+    }
+
+    /// Replaces the alpha with `alpha` in `0.0..=1.0`, like `Fade` and
+    /// `ColorAlpha`.
+    pub fn fade(self, alpha: f32) -> Self {
+        Self {
+            a: (255.0 * alpha.clamp(0.0, 1.0)) as u8,
+            ..self
+        }
+    }
+
+    /// Interpolates towards `other` by `factor` in `0.0..=1.0`, like
+    /// `ColorLerp`.
+    pub fn lerp(self, other: Self, factor: f32) -> Self {
+        let factor = factor.clamp(0.0, 1.0);
+        let channel = |a: u8, b: u8| ((1.0 - factor) * f32::from(a) + factor * f32::from(b)) as u8;
+
+        Self::new(
+            channel(self.r, other.r),
+            channel(self.g, other.g),
+            channel(self.b, other.b),
+            channel(self.a, other.a),
+        )
+    }
+
+    /// Multiplies each channel by `tint`'s, like `ColorTint`.
+    pub fn tint(self, tint: Self) -> Self {
+        let channel = |c: u8, t: u8| (f32::from(c) / 255.0 * (f32::from(t) / 255.0) * 255.0) as u8;
+
+        Self::new(
+            channel(self.r, tint.r),
+            channel(self.g, tint.g),
+            channel(self.b, tint.b),
+            channel(self.a, tint.a),
+        )
+    }
+
+    /// Draws `src` over this color with alpha blending.
+    pub fn blend(self, src: Self) -> Self {
+        self.blend_tinted(src, crate::WHITE)
+    }
+
+    /// Tints `src` and draws it over this color with alpha blending, like
+    /// `ColorAlphaBlend`.
+    pub fn blend_tinted(self, src: Self, tint: Self) -> Self {
+        let dst = self;
+        let tinted = |c: u8, t: u8| ((u32::from(c) * (u32::from(t) + 1)) >> 8) as u8;
+        let src = Self::new(
+            tinted(src.r, tint.r),
+            tinted(src.g, tint.g),
+            tinted(src.b, tint.b),
+            tinted(src.a, tint.a),
+        );
+
+        match src.a {
+            0 => dst,
+            255 => src,
+            _ => {
+                // Shifting divides by 256, so alpha is offset to cover 255.
+                let alpha = u32::from(src.a) + 1;
+                let dst_a = u32::from(dst.a);
+                let out_a = (alpha * 256 + dst_a * (256 - alpha)) >> 8;
+
+                let channel = |s: u8, d: u8| {
+                    (((u32::from(s) * alpha * 256 + u32::from(d) * dst_a * (256 - alpha)) / out_a)
+                        >> 8) as u8
+                };
+
+                Self::new(
+                    channel(src.r, dst.r),
+                    channel(src.g, dst.g),
+                    channel(src.b, dst.b),
+                    out_a as u8,
+                )
+            }
+        }
+    }
+
+    /// Brightens towards white for a positive `factor` or darkens towards
+    /// black for a negative one, in `-1.0..=1.0`, like `ColorBrightness`.
+    pub fn brightness(self, factor: f32) -> Self {
+        let factor = factor.clamp(-1.0, 1.0);
+        let channel = |c: u8| {
+            let c = f32::from(c);
+
+            if factor < 0.0 {
+                (c * (1.0 + factor)) as u8
+            } else {
+                ((255.0 - c) * factor + c) as u8
+            }
+        };
+
+        Self::new(channel(self.r), channel(self.g), channel(self.b), self.a)
+    }
+
+    /// Adjusts contrast by `contrast` in `-1.0..=1.0`, like `ColorContrast`.
+    pub fn contrast(self, contrast: f32) -> Self {
+        let contrast = (1.0 + contrast.clamp(-1.0, 1.0)).powi(2);
+        let channel = |c: u8| {
+            let c = (f32::from(c) / 255.0 - 0.5) * contrast + 0.5;
+            (c * 255.0).clamp(0.0, 255.0) as u8
+        };
+
+        Self::new(channel(self.r), channel(self.g), channel(self.b), self.a)
+    }
+
+    /// The hue in degrees, and the smallest and largest normalized channel.
+    fn hue(self) -> (f32, f32, f32) {
+        let Vector4 {
+            x: r, y: g, z: b, ..
+        } = self.normalize();
+        let min = r.min(g).min(b);
+        let max = r.max(g).max(b);
+        let delta = max - min;
+
+        if delta < 0.00001 {
+            return (0.0, min, max);
+        }
+
+        let sector = if r >= max {
+            (g - b) / delta
+        } else if g >= max {
+            2.0 + (b - r) / delta
+        } else {
+            4.0 + (r - g) / delta
+        };
+
+        let hue = sector * 60.0;
+        (if hue < 0.0 { hue + 360.0 } else { hue }, min, max)
+    }
+}
+
+/// Compares channels exactly, like `ColorIsEqual`.
+impl PartialEq for Color {
+    fn eq(&self, other: &Self) -> bool {
+        self.to_u32() == other.to_u32()
+    }
+}
+
+impl Eq for Color {}
+
+impl Hash for Color {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.to_u32().hash(state);
+    }
+}
+
+impl FromStr for Color {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::from_hex(s)
+    }
+}
+
+/// Formats as `rrggbbaa`, so `{:#010x}` gives `0xrrggbbaa`.
+impl fmt::LowerHex for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::LowerHex::fmt(&self.to_u32(), f)
+    }
+}
+
+/// Formats as `RRGGBBAA`, so `{:#010X}` gives `0xRRGGBBAA`.
+impl fmt::UpperHex for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::UpperHex::fmt(&self.to_u32(), f)
+    }
+}
+
+/// The sRGB transfer function, from encoded to linear.
+pub(crate) fn srgb_to_linear(c: f32) -> f32 {
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// The inverse sRGB transfer function, from linear to encoded.
+pub(crate) fn linear_to_srgb(c: f32) -> f32 {
+    if c <= 0.0031308 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sys;
+
+    const FACTORS: [f32; 11] = [-1.5, -1.0, -0.5, -0.01, 0.0, 0.1, 0.25, 0.5, 0.75, 1.0, 1.5];
+
+    /// Every combination of some edge and middle channel values.
+    fn colors() -> Vec<Color> {
+        const CHANNELS: [u8; 6] = [0, 1, 64, 127, 128, 255];
+
+        CHANNELS
+            .iter()
+            .flat_map(|&r| CHANNELS.map(move |g| (r, g)))
+            .flat_map(|(r, g)| CHANNELS.map(move |b| (r, g, b)))
+            .flat_map(|(r, g, b)| CHANNELS.map(move |a| Color::new(r, g, b, a)))
+            .chain((0..256u32).map(|i| Color::from_u32(i.wrapping_mul(0x9E37_79B9))))
+            .collect()
+    }
+
+    fn assert_vector4_eq(rust: Vector4, c: Vector4, input: Color) {
+        let close = |a: f32, b: f32| (a - b).abs() <= 1e-6;
+
+        assert!(
+            close(rust.x, c.x) && close(rust.y, c.y) && close(rust.z, c.z) && close(rust.w, c.w),
+            "{input:?}: {rust:?} != {c:?}"
+        );
+    }
+
+    #[test]
+    fn matches_raylib_packing() {
+        for color in colors() {
+            let packed = color.to_u32();
+
+            assert_eq!(
+                packed,
+                unsafe { sys::ColorToInt(color) } as u32,
+                "{color:?}"
+            );
+            assert_eq!(Color::from_u32(packed), unsafe { sys::GetColor(packed) });
+            assert_vector4_eq(
+                color.normalize(),
+                unsafe { sys::ColorNormalize(color) },
+                color,
+            );
+            assert_eq!(
+                Color::from_normalized(color.normalize()),
+                unsafe { sys::ColorFromNormalized(color.normalize()) },
+                "{color:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn matches_raylib_hsv() {
+        for color in colors() {
+            let rust = color.to_hsv();
+            let c = unsafe { sys::ColorToHSV(color) };
+
+            assert_vector4_eq(
+                Vector4::new(rust.x, rust.y, rust.z, 0.0),
+                Vector4::new(c.x, c.y, c.z, 0.0),
+                color,
+            );
+        }
+
+        for hue in (0..=720).step_by(15).map(|h| h as f32) {
+            for saturation in [0.0, 0.25, 0.5, 0.9, 1.0] {
+                for value in [0.0, 0.3, 0.5, 0.99, 1.0] {
+                    assert_eq!(
+                        Color::from_hsv(hue, saturation, value),
+                        unsafe { sys::ColorFromHSV(hue, saturation, value) },
+                        "hsv({hue}, {saturation}, {value})"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn matches_raylib_per_color_operations() {
+        for color in colors() {
+            for factor in FACTORS {
+                assert_eq!(
+                    color.fade(factor),
+                    unsafe { sys::Fade(color, factor) },
+                    "{color:?}.fade({factor})"
+                );
+                assert_eq!(
+                    color.brightness(factor),
+                    unsafe { sys::ColorBrightness(color, factor) },
+                    "{color:?}.brightness({factor})"
+                );
+                assert_eq!(
+                    color.contrast(factor),
+                    unsafe { sys::ColorContrast(color, factor) },
+                    "{color:?}.contrast({factor})"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn matches_raylib_two_color_operations() {
+        let colors = colors();
+        let others: Vec<Color> = colors.iter().copied().step_by(13).collect();
+
+        for &a in &colors {
+            for &b in &others {
+                assert_eq!(
+                    a.tint(b),
+                    unsafe { sys::ColorTint(a, b) },
+                    "{a:?}.tint({b:?})"
+                );
+
+                for factor in FACTORS {
+                    assert_eq!(
+                        a.lerp(b, factor),
+                        unsafe { sys::ColorLerp(a, b, factor) },
+                        "{a:?}.lerp({b:?}, {factor})"
+                    );
+                }
+
+                for tint in [crate::WHITE, crate::BLANK, Color::new(255, 128, 0, 200)] {
+                    assert_eq!(
+                        a.blend_tinted(b, tint),
+                        unsafe { sys::ColorAlphaBlend(a, b, tint) },
+                        "{a:?}.blend_tinted({b:?}, {tint:?})"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn hex_round_trips() {
+        for color in colors() {
+            assert_eq!(Color::from_hex(&color.to_hex()).unwrap(), color);
+            assert_eq!(color.to_hex().parse::<Color>().unwrap(), color);
+            assert_eq!(Color::from_hex(&format!("{color:08x}")).unwrap(), color);
+        }
+
+        assert_eq!(
+            Color::from_hex("#1a2B3c").unwrap(),
+            Color::rgb(0x1A, 0x2B, 0x3C)
+        );
+        assert_eq!(
+            Color::from_hex("abc").unwrap(),
+            Color::rgb(0xAA, 0xBB, 0xCC)
+        );
+        assert_eq!(
+            Color::from_hex("#abcd").unwrap(),
+            Color::new(0xAA, 0xBB, 0xCC, 0xDD)
+        );
+        assert_eq!(Color::rgb(1, 2, 3).to_hex(), "#010203FF");
+        assert_eq!(format!("{:#010X}", Color::new(1, 2, 3, 4)), "0x01020304");
+    }
+
+    #[test]
+    fn hex_rejects_invalid_input() {
+        for input in [
+            "",
+            "#",
+            "#12",
+            "12345",
+            "#12345",
+            "1234567",
+            "123456789",
+            "#123456789",
+            "##123456",
+            "#12345g",
+            "+12345",
+            "-1234567",
+            "12 34 56",
+            "#ÿÿÿ",
+        ] {
+            assert!(
+                matches!(Color::from_hex(input), Err(Error::ParseColor { input: ref i }) if i == input),
+                "{input:?} was accepted"
+            );
+        }
+    }
+
+    #[test]
+    fn hsl_and_linear_round_trip() {
+        for color in colors().into_iter().map(|c| Color { a: 255, ..c }) {
+            let hsl = color.to_hsl();
+            assert_eq!(Color::from_hsl(hsl.x, hsl.y, hsl.z), color, "{hsl:?}");
+            assert_eq!(Color::from_linear(color.to_linear()), color);
+        }
+    }
+}
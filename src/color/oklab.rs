@@ -0,0 +1,264 @@
+use crate::sys::{Color, Vector3, Vector4};
+
+/// The color space colors are interpolated in.
+#[derive(Debug, Default, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum ColorSpace {
+    /// Interpolates the encoded channels directly, like `ColorLerp` but
+    /// rounding rather than truncating. Cheap, but midpoints between
+    /// saturated colors come out dark and muddy.
+    #[default]
+    Srgb,
+    /// Interpolates linear light, which blends like physical light does.
+    Linear,
+    /// Interpolates in OKLab, which keeps perceived lightness even.
+    Oklab,
+    /// Interpolates lightness, chroma, and hue in OKLCH, taking the shorter
+    /// way around the hue circle. Keeps gradients between saturated colors
+    /// vivid.
+    Oklch,
+}
+
+impl Color {
+    /// Converts to OKLab lightness, green-red, and blue-yellow axes. Alpha
+    /// is ignored.
+    pub fn to_oklab(self) -> Vector3 {
+        // The published matrices carry more precision than f32 holds.
+        let Vector4 { x, y, z, .. } = self.to_linear();
+        let (r, g, b) = (f64::from(x), f64::from(y), f64::from(z));
+
+        let l = (0.4122214708 * r + 0.5363325363 * g + 0.0514459929 * b).cbrt();
+        let m = (0.2119034982 * r + 0.6806995451 * g + 0.1073969566 * b).cbrt();
+        let s = (0.0883024619 * r + 0.2817188376 * g + 0.6299787005 * b).cbrt();
+
+        Vector3 {
+            x: (0.2104542553 * l + 0.7936177850 * m - 0.0040720468 * s) as f32,
+            y: (1.9779984951 * l - 2.4285922050 * m + 0.4505937099 * s) as f32,
+            z: (0.0259040371 * l + 0.7827717662 * m - 0.8086757660 * s) as f32,
+        }
+    }
+
+    /// Creates an opaque color from OKLab coordinates. Colors outside the
+    /// sRGB gamut are clamped.
+    pub fn from_oklab(lightness: f32, a: f32, b: f32) -> Self {
+        let (lightness, a, b) = (f64::from(lightness), f64::from(a), f64::from(b));
+
+        let l = (lightness + 0.3963377774 * a + 0.2158037573 * b).powi(3);
+        let m = (lightness - 0.1055613458 * a - 0.0638541728 * b).powi(3);
+        let s = (lightness - 0.0894841775 * a - 1.2914855480 * b).powi(3);
+
+        Self::from_linear(Vector4 {
+            x: (4.0767416621 * l - 3.3077115913 * m + 0.2309699292 * s) as f32,
+            y: (-1.2684380046 * l + 2.6097574011 * m - 0.3413193965 * s) as f32,
+            z: (-0.0041960863 * l - 0.7034186147 * m + 1.7076147010 * s) as f32,
+            w: 1.0,
+        })
+    }
+
+    /// Converts to OKLCH lightness, chroma, and hue (in degrees). Alpha is
+    /// ignored.
+    pub fn to_oklch(self) -> Vector3 {
+        let lab = self.to_oklab();
+
+        Vector3 {
+            x: lab.x,
+            y: lab.y.hypot(lab.z),
+            z: lab.z.atan2(lab.y).to_degrees().rem_euclid(360.0),
+        }
+    }
+
+    /// Creates an opaque color from OKLCH lightness, chroma, and hue (in
+    /// degrees). Colors outside the sRGB gamut are clamped.
+    pub fn from_oklch(lightness: f32, chroma: f32, hue: f32) -> Self {
+        let (sin, cos) = hue.to_radians().sin_cos();
+        Self::from_oklab(lightness, chroma * cos, chroma * sin)
+    }
+
+    /// Interpolates towards `other` by `factor` in `0.0..=1.0`, in `space`.
+    /// Alpha is always interpolated linearly.
+    pub fn mix(self, other: Self, factor: f32, space: ColorSpace) -> Self {
+        let t = factor.clamp(0.0, 1.0);
+        let alpha = lerp(f32::from(self.a), f32::from(other.a), t).round() as u8;
+
+        let mixed = match space {
+            ColorSpace::Srgb => {
+                let channel = |a: u8, b: u8| lerp(f32::from(a), f32::from(b), t).round() as u8;
+                Self::rgb(
+                    channel(self.r, other.r),
+                    channel(self.g, other.g),
+                    channel(self.b, other.b),
+                )
+            }
+            ColorSpace::Linear => {
+                let (a, b) = (self.to_linear(), other.to_linear());
+
+                Self::from_linear(Vector4 {
+                    x: lerp(a.x, b.x, t),
+                    y: lerp(a.y, b.y, t),
+                    z: lerp(a.z, b.z, t),
+                    w: 1.0,
+                })
+            }
+            ColorSpace::Oklab => {
+                let (a, b) = (self.to_oklab(), other.to_oklab());
+                Self::from_oklab(lerp(a.x, b.x, t), lerp(a.y, b.y, t), lerp(a.z, b.z, t))
+            }
+            ColorSpace::Oklch => {
+                let (a, b) = (self.to_oklch(), other.to_oklch());
+
+                // Grays have no meaningful hue, so take the other color's
+                // rather than sweeping through an arbitrary one.
+                let (from_hue, to_hue) = match (a.y < ACHROMATIC, b.y < ACHROMATIC) {
+                    (true, false) => (b.z, b.z),
+                    (false, true) => (a.z, a.z),
+                    _ => (a.z, b.z),
+                };
+
+                let delta = (to_hue - from_hue + 180.0).rem_euclid(360.0) - 180.0;
+                Self::from_oklch(lerp(a.x, b.x, t), lerp(a.y, b.y, t), from_hue + delta * t)
+            }
+        };
+
+        Self { a: alpha, ..mixed }
+    }
+
+    /// The perceptual distance to `other` in OKLab, ignoring alpha. A
+    /// difference of about 0.02 is just noticeable.
+    pub fn distance(self, other: Self) -> f32 {
+        oklab_distance(self.to_oklab(), other.to_oklab())
+    }
+}
+
+/// Chroma below which a color is treated as gray.
+const ACHROMATIC: f32 = 0.0001;
+
+fn lerp(a: f32, b: f32, t: f32) -> f32 {
+    a + (b - a) * t
+}
+
+pub(super) fn oklab_distance(a: Vector3, b: Vector3) -> f32 {
+    let (dl, da, db) = (a.x - b.x, a.y - b.y, a.z - b.z);
+    (dl * dl + da * da + db * db).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BLACK, BLUE, RED, WHITE};
+
+    const SPACES: [ColorSpace; 4] = [
+        ColorSpace::Srgb,
+        ColorSpace::Linear,
+        ColorSpace::Oklab,
+        ColorSpace::Oklch,
+    ];
+
+    fn assert_close(actual: Vector3, expected: [f32; 3]) {
+        let [x, y, z] = expected;
+
+        assert!(
+            (actual.x - x).abs() < 1e-4
+                && (actual.y - y).abs() < 1e-4
+                && (actual.z - z).abs() < 1e-4,
+            "{actual:?} != {expected:?}"
+        );
+    }
+
+    fn colors() -> impl Iterator<Item = Color> {
+        (0..512u32).map(|i| Color::from_u32(i.wrapping_mul(0x9E37_79B9) | 0xFF))
+    }
+
+    #[test]
+    fn oklab_reference_values() {
+        assert_close(WHITE.to_oklab(), [1.0, 0.0, 0.0]);
+        assert_close(BLACK.to_oklab(), [0.0, 0.0, 0.0]);
+        assert_close(
+            Color::rgb(255, 0, 0).to_oklab(),
+            [0.62796, 0.22486, 0.12585],
+        );
+        assert_close(
+            Color::rgb(0, 255, 0).to_oklab(),
+            [0.86644, -0.23389, 0.17950],
+        );
+        assert_close(
+            Color::rgb(0, 0, 255).to_oklab(),
+            [0.45201, -0.03246, -0.31153],
+        );
+    }
+
+    #[test]
+    fn oklab_and_oklch_round_trip() {
+        for color in colors() {
+            let lab = color.to_oklab();
+            let lch = color.to_oklch();
+
+            assert_eq!(Color::from_oklab(lab.x, lab.y, lab.z), color);
+            assert_eq!(Color::from_oklch(lch.x, lch.y, lch.z), color);
+            assert!((0.0..360.0).contains(&lch.z), "{lch:?}");
+        }
+    }
+
+    #[test]
+    fn mix_endpoints_are_the_inputs() {
+        let pairs = [
+            (RED, BLUE),
+            (BLACK, WHITE),
+            (Color::new(12, 200, 99, 0), Color::new(250, 3, 140, 255)),
+            (Color::new(128, 128, 128, 64), Color::new(30, 60, 90, 192)),
+        ];
+
+        for space in SPACES {
+            for (a, b) in pairs {
+                assert_eq!(a.mix(b, 0.0, space), a, "{space:?}");
+                assert_eq!(a.mix(b, 1.0, space), b, "{space:?}");
+                assert_eq!(a.mix(b, -1.0, space), a, "{space:?}");
+                assert_eq!(a.mix(b, 2.0, space), b, "{space:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn mix_interpolates_alpha_linearly() {
+        let (a, b) = (Color::new(255, 0, 0, 0), Color::new(0, 0, 255, 255));
+
+        for space in SPACES {
+            assert_eq!(a.mix(b, 0.5, space).a, 128, "{space:?}");
+        }
+    }
+
+    #[test]
+    fn srgb_mix_rounds() {
+        let mixed = BLACK.mix(WHITE, 0.5, ColorSpace::Srgb);
+
+        assert_eq!(mixed, Color::rgb(128, 128, 128));
+        assert_eq!(BLACK.lerp(WHITE, 0.5), Color::rgb(127, 127, 127));
+    }
+
+    #[test]
+    fn oklch_keeps_the_hue_of_the_saturated_color_when_mixing_with_gray() {
+        let hue = RED.to_oklch().z;
+        let mixed = RED.mix(WHITE, 0.5, ColorSpace::Oklch).to_oklch();
+
+        assert!((mixed.z - hue).abs() < 2.0, "{} != {hue}", mixed.z);
+    }
+
+    #[test]
+    fn oklch_takes_the_shorter_way_around_the_hue_circle() {
+        // Violet (about 315 degrees) and orange (about 53) are closest
+        // across 0, so the midpoint is red rather than green.
+        let (a, b) = (Color::rgb(200, 0, 255), Color::rgb(255, 128, 0));
+        let mixed = a.mix(b, 0.5, ColorSpace::Oklch);
+        let hue = mixed.to_oklch().z;
+
+        assert!(!(90.0..270.0).contains(&hue), "{mixed:?} has hue {hue}");
+        assert!(mixed.r > mixed.g, "{mixed:?}");
+    }
+
+    #[test]
+    fn distance_is_zero_for_equal_colors() {
+        for color in colors() {
+            assert_eq!(color.distance(color), 0.0);
+        }
+
+        assert!((BLACK.distance(WHITE) - 1.0).abs() < 1e-4);
+    }
+}
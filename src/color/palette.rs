@@ -0,0 +1,433 @@
+use super::oklab::oklab_distance;
+use super::ColorSpace;
+use crate::error::{Error, Result};
+use crate::image::OwnedImage;
+use crate::sys;
+use crate::sys::{Color, Vector3};
+use std::fmt::Write;
+use std::os::raw::c_int;
+use std::path::Path;
+use std::slice;
+
+/// An ordered list of colors, optionally named.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Palette {
+    name: Option<String>,
+    colors: Vec<Color>,
+}
+
+impl Palette {
+    pub fn new(colors: impl Into<Vec<Color>>) -> Self {
+        Self {
+            name: None,
+            colors: colors.into(),
+        }
+    }
+
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Collects up to `max_size` distinct colors from an image, in the order
+    /// they first appear, like `LoadImagePalette`.
+    pub fn from_image(image: &OwnedImage, max_size: usize) -> Self {
+        let max_size = c_int::try_from(max_size).unwrap_or(c_int::MAX);
+        let mut count = 0;
+
+        let colors = unsafe {
+            let colors = sys::LoadImagePalette(*image.as_raw(), max_size, &mut count);
+
+            if colors.is_null() {
+                return Self::default();
+            }
+
+            let copy = slice::from_raw_parts(colors, count.max(0) as usize).to_vec();
+            sys::UnloadImagePalette(colors);
+            copy
+        };
+
+        Self::new(colors)
+    }
+
+    /// `steps` colors spaced evenly along a gradient through `stops`,
+    /// interpolated in `space`.
+    pub fn gradient(stops: &[Color], steps: usize, space: ColorSpace) -> Self {
+        let colors = match (stops, steps) {
+            ([], _) | (_, 0) => Vec::new(),
+            ([color], _) => vec![*color; steps],
+            (_, 1) => vec![stops[0]],
+            _ => (0..steps)
+                .map(|i| {
+                    let position = i as f32 / (steps - 1) as f32 * (stops.len() - 1) as f32;
+                    let index = (position as usize).min(stops.len() - 2);
+                    stops[index].mix(stops[index + 1], position - index as f32, space)
+                })
+                .collect(),
+        };
+
+        Self::new(colors)
+    }
+
+    /// Loads a GIMP (`.gpl`) or plain hex (`.hex`) palette, chosen by the
+    /// file's extension.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let format = PaletteFormat::from_path(path)?;
+        let text = std::fs::read_to_string(path)?;
+
+        match format {
+            PaletteFormat::Gpl => parse_gpl(path, &text),
+            PaletteFormat::Hex => parse_hex(path, &text),
+        }
+    }
+
+    /// Saves the palette as GIMP (`.gpl`) or plain hex (`.hex`), chosen by
+    /// the file's extension.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+
+        let text = match PaletteFormat::from_path(path)? {
+            PaletteFormat::Gpl => self.to_gpl(),
+            PaletteFormat::Hex => self.to_hex(),
+        };
+
+        std::fs::write(path, text)?;
+        Ok(())
+    }
+
+    /// Parses a GIMP palette. Colors are opaque, as the format has no alpha.
+    pub fn from_gpl(text: &str) -> Result<Self> {
+        parse_gpl(Path::new("<memory>"), text)
+    }
+
+    /// Formats the palette as a GIMP palette. Alpha is dropped.
+    pub fn to_gpl(&self) -> String {
+        let mut text = String::from("GIMP Palette\n");
+
+        if let Some(name) = &self.name {
+            let _ = writeln!(text, "Name: {name}");
+        }
+
+        text.push_str("#\n");
+
+        for color in &self.colors {
+            let _ = writeln!(
+                text,
+                "{:3} {:3} {:3}\t#{:06X}",
+                color.r,
+                color.g,
+                color.b,
+                color.to_u32() >> 8
+            );
+        }
+
+        text
+    }
+
+    /// Parses a plain hex palette: one `RRGGBB` or `RRGGBBAA` color per
+    /// line, as used by Lospec.
+    pub fn from_hex(text: &str) -> Result<Self> {
+        parse_hex(Path::new("<memory>"), text)
+    }
+
+    /// Formats the palette as plain hex, one color per line. Alpha is only
+    /// written for colors that aren't opaque.
+    pub fn to_hex(&self) -> String {
+        let mut text = String::new();
+
+        for color in &self.colors {
+            let _ = match color.a {
+                255 => writeln!(text, "{:06x}", color.to_u32() >> 8),
+                _ => writeln!(text, "{color:08x}"),
+            };
+        }
+
+        text
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    pub fn colors(&self) -> &[Color] {
+        &self.colors
+    }
+
+    pub fn len(&self) -> usize {
+        self.colors.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.colors.is_empty()
+    }
+
+    pub fn push(&mut self, color: Color) {
+        self.colors.push(color);
+    }
+
+    /// The index of the perceptually nearest color, compared in OKLab and
+    /// ignoring alpha, or `None` if the palette is empty.
+    pub fn nearest_index(&self, color: Color) -> Option<usize> {
+        self.matcher()(color)
+    }
+
+    /// The perceptually nearest color, compared in OKLab and ignoring alpha,
+    /// or `None` if the palette is empty.
+    pub fn nearest(&self, color: Color) -> Option<Color> {
+        self.nearest_index(color).map(|i| self.colors[i])
+    }
+
+    /// Returns a function finding the index of the nearest color, with the
+    /// palette converted to OKLab once up front.
+    pub(crate) fn matcher(&self) -> impl Fn(Color) -> Option<usize> {
+        let labs: Vec<Vector3> = self.colors.iter().map(|c| c.to_oklab()).collect();
+
+        move |color| {
+            let lab = color.to_oklab();
+
+            labs.iter()
+                .map(|&other| oklab_distance(lab, other))
+                .enumerate()
+                .min_by(|(_, a), (_, b)| a.total_cmp(b))
+                .map(|(index, _)| index)
+        }
+    }
+}
+
+impl From<Vec<Color>> for Palette {
+    fn from(colors: Vec<Color>) -> Self {
+        Self::new(colors)
+    }
+}
+
+impl FromIterator<Color> for Palette {
+    fn from_iter<I: IntoIterator<Item = Color>>(iter: I) -> Self {
+        Self::new(iter.into_iter().collect::<Vec<_>>())
+    }
+}
+
+#[derive(Copy, Clone)]
+enum PaletteFormat {
+    Gpl,
+    Hex,
+}
+
+impl PaletteFormat {
+    fn from_path(path: &Path) -> Result<Self> {
+        let extension = path.extension().and_then(|ext| ext.to_str());
+
+        match extension.map(str::to_ascii_lowercase).as_deref() {
+            Some("gpl") => Ok(Self::Gpl),
+            Some("hex") => Ok(Self::Hex),
+            _ => Err(Error::UnsupportedPaletteFormat(path.to_path_buf())),
+        }
+    }
+}
+
+fn parse_gpl(path: &Path, text: &str) -> Result<Palette> {
+    let error = |line: usize, message: &str| Error::ParsePalette {
+        path: path.to_path_buf(),
+        line,
+        message: message.to_owned(),
+    };
+
+    let mut lines = text
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()));
+
+    if lines.next().map(|(_, line)| line) != Some("GIMP Palette") {
+        return Err(error(1, "missing 'GIMP Palette' header"));
+    }
+
+    let mut palette = Palette::default();
+
+    for (number, line) in lines {
+        if line.is_empty() || line.starts_with('#') || line.starts_with("Columns:") {
+            continue;
+        }
+
+        if let Some(name) = line.strip_prefix("Name:") {
+            palette.name = Some(name.trim().to_owned());
+            continue;
+        }
+
+        // Anything after the three channels is the color's name.
+        let mut channels = line.split_whitespace().map(str::parse::<u8>);
+
+        match (channels.next(), channels.next(), channels.next()) {
+            (Some(Ok(r)), Some(Ok(g)), Some(Ok(b))) => palette.push(Color::rgb(r, g, b)),
+            _ => return Err(error(number, "expected three channels from 0 to 255")),
+        }
+    }
+
+    Ok(palette)
+}
+
+fn parse_hex(path: &Path, text: &str) -> Result<Palette> {
+    let mut palette = Palette::default();
+
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+
+        if line.is_empty() {
+            continue;
+        }
+
+        match Color::from_hex(line) {
+            Ok(color) if matches!(line.trim_start_matches('#').len(), 6 | 8) => palette.push(color),
+            _ => {
+                return Err(Error::ParsePalette {
+                    path: path.to_path_buf(),
+                    line: index + 1,
+                    message: format!("invalid color '{line}'"),
+                });
+            }
+        }
+    }
+
+    Ok(palette)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BLACK, BLUE, RED, WHITE};
+
+    fn palette() -> Palette {
+        Palette::new(vec![BLACK, WHITE, Color::rgb(0x12, 0xAB, 0xEF), RED])
+    }
+
+    fn parse_error(result: Result<Palette>) -> (usize, String) {
+        match result {
+            Err(Error::ParsePalette { line, message, .. }) => (line, message),
+            other => panic!("expected a parse error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn gpl_round_trips() {
+        let palette = palette().with_name("Test Colors");
+        let text = palette.to_gpl();
+
+        assert!(text.starts_with("GIMP Palette\nName: Test Colors\n"));
+        assert!(text.contains(" 18 171 239\t#12ABEF\n"));
+        assert_eq!(Palette::from_gpl(&text).unwrap(), palette);
+    }
+
+    #[test]
+    fn gpl_skips_comments_and_columns_and_keeps_color_names() {
+        let text =
+            "GIMP Palette\nName: Named\nColumns: 4\n# comment\n\n255 0 0 Red\n  0 0 255\tBlue\n";
+        let palette = Palette::from_gpl(text).unwrap();
+
+        assert_eq!(palette.name(), Some("Named"));
+        assert_eq!(
+            palette.colors(),
+            [Color::rgb(255, 0, 0), Color::rgb(0, 0, 255)]
+        );
+    }
+
+    #[test]
+    fn gpl_errors_report_the_line() {
+        assert_eq!(parse_error(Palette::from_gpl("")).0, 1);
+        assert_eq!(parse_error(Palette::from_gpl("255 0 0\n")).0, 1);
+
+        let text = "GIMP Palette\n# comment\n255 0 0\n255 0\n";
+        assert_eq!(
+            parse_error(Palette::from_gpl(text)),
+            (4, "expected three channels from 0 to 255".to_owned())
+        );
+
+        assert_eq!(
+            parse_error(Palette::from_gpl("GIMP Palette\n0 256 0\n")).0,
+            2
+        );
+        assert_eq!(
+            parse_error(Palette::from_gpl("GIMP Palette\nred green blue\n")).0,
+            2
+        );
+    }
+
+    #[test]
+    fn hex_round_trips() {
+        let mut palette = palette();
+        palette.push(Color::new(1, 2, 3, 4));
+        let text = palette.to_hex();
+
+        assert_eq!(text, "000000\nffffff\n12abef\ne62937\n01020304\n");
+        assert_eq!(Palette::from_hex(&text).unwrap(), palette);
+    }
+
+    #[test]
+    fn hex_errors_report_the_line() {
+        assert_eq!(
+            parse_error(Palette::from_hex("000000\n\nfff\n")),
+            (3, "invalid color 'fff'".to_owned())
+        );
+        assert_eq!(parse_error(Palette::from_hex("12345g")).0, 1);
+        assert_eq!(parse_error(Palette::from_hex("000000\n1234567\n")).0, 2);
+        assert_eq!(Palette::from_hex("#FFFFFF\n").unwrap().colors(), [WHITE]);
+    }
+
+    #[test]
+    fn load_and_save_by_extension() {
+        let dir = std::env::temp_dir().join(format!("rraylib-palette-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let palette = palette().with_name("Saved");
+
+        for name in ["colors.gpl", "colors.HEX"] {
+            let path = dir.join(name);
+            palette.save(&path).unwrap();
+
+            let loaded = Palette::load(&path).unwrap();
+            assert_eq!(loaded.colors(), palette.colors());
+        }
+
+        for name in ["colors.txt", "colors"] {
+            let path = dir.join(name);
+
+            assert!(matches!(
+                palette.save(&path),
+                Err(Error::UnsupportedPaletteFormat(ref p)) if *p == path
+            ));
+            assert!(matches!(
+                Palette::load(&path),
+                Err(Error::UnsupportedPaletteFormat(ref p)) if *p == path
+            ));
+        }
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn gradient_spans_the_stops() {
+        let gradient = Palette::gradient(&[RED, BLUE], 5, ColorSpace::Oklab);
+
+        assert_eq!(gradient.len(), 5);
+        assert_eq!(gradient.colors()[0], RED);
+        assert_eq!(gradient.colors()[4], BLUE);
+
+        assert!(Palette::gradient(&[], 5, ColorSpace::Srgb).is_empty());
+        assert!(Palette::gradient(&[RED, BLUE], 0, ColorSpace::Srgb).is_empty());
+        assert_eq!(
+            Palette::gradient(&[RED], 3, ColorSpace::Srgb).colors(),
+            [RED; 3]
+        );
+        assert_eq!(
+            Palette::gradient(&[RED, BLUE], 1, ColorSpace::Srgb).colors(),
+            [RED]
+        );
+    }
+
+    #[test]
+    fn nearest_matches_perceptually() {
+        let palette = palette();
+
+        assert_eq!(palette.nearest_index(Color::rgb(10, 10, 10)), Some(0));
+        assert_eq!(palette.nearest(Color::rgb(250, 250, 240)), Some(WHITE));
+        assert_eq!(palette.nearest(Color::rgb(200, 30, 40)), Some(RED));
+        assert_eq!(Palette::default().nearest(RED), None);
+    }
+}
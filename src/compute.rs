@@ -0,0 +1,341 @@
+use crate::error::{self, Error, ResourceKind, Result};
+use crate::log;
+use crate::shader::UniformElement;
+use crate::sys;
+use crate::texture::OwnedTexture;
+use crate::window::Window;
+use bytemuck::Pod;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::ffi::{CStr, CString};
+use std::io;
+use std::marker::PhantomData;
+use std::mem::size_of;
+use std::os::raw::{c_int, c_void};
+use std::path::{Path, PathBuf};
+
+/// How a shader storage buffer is expected to be used, as a hint to the
+/// driver.
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum BufferUsage {
+    StreamDraw,
+    StreamRead,
+    StreamCopy,
+    StaticDraw,
+    StaticRead,
+    StaticCopy,
+    DynamicDraw,
+    DynamicRead,
+    DynamicCopy,
+}
+
+impl BufferUsage {
+    const fn to_gl(self) -> c_int {
+        (match self {
+            Self::StreamDraw => sys::RL_STREAM_DRAW,
+            Self::StreamRead => sys::RL_STREAM_READ,
+            Self::StreamCopy => sys::RL_STREAM_COPY,
+            Self::StaticDraw => sys::RL_STATIC_DRAW,
+            Self::StaticRead => sys::RL_STATIC_READ,
+            Self::StaticCopy => sys::RL_STATIC_COPY,
+            Self::DynamicDraw => sys::RL_DYNAMIC_DRAW,
+            Self::DynamicRead => sys::RL_DYNAMIC_READ,
+            Self::DynamicCopy => sys::RL_DYNAMIC_COPY,
+        }) as c_int
+    }
+}
+
+/// How a compute shader may access a bound image texture.
+///
+/// rlgl only distinguishes read-only from read-write access.
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum ImageAccess {
+    ReadOnly,
+    ReadWrite,
+}
+
+/// A compiled compute shader program, unloaded when dropped.
+///
+/// Like [`OwnedTexture`], it borrows the [`Window`] whose OpenGL context it
+/// lives in.
+#[derive(Debug)]
+pub struct ComputeShader<'w> {
+    id: u32,
+    locations: RefCell<HashMap<String, i32>>,
+    _window: PhantomData<&'w Window>,
+}
+
+impl<'w> ComputeShader<'w> {
+    /// Compiles and links a compute shader from a source file.
+    pub fn load(_window: &'w Window, path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let c_path = crate::fs::to_cstring(path)?;
+
+        let source = unsafe {
+            let text = sys::LoadFileText(c_path.as_ptr());
+
+            if text.is_null() {
+                return Err(Error::Io(io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("shader source not found: {}", path.display()),
+                )));
+            }
+
+            let source = CStr::from_ptr(text).to_owned();
+            sys::UnloadFileText(text);
+            source
+        };
+
+        Self::compile(&source, path)
+    }
+
+    /// Compiles and links a compute shader from source code.
+    pub fn from_source(_window: &'w Window, code: &str) -> Result<Self> {
+        let code =
+            CString::new(code).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+        Self::compile(&code, Path::new("<memory>"))
+    }
+
+    fn compile(code: &CStr, path: &Path) -> Result<Self> {
+        let (id, mut messages) = log::capture(|| unsafe {
+            let shader = sys::rlCompileShader(code.as_ptr(), sys::RL_COMPUTE_SHADER as c_int);
+
+            if shader == 0 {
+                0
+            } else {
+                sys::rlLoadComputeShaderProgram(shader)
+            }
+        });
+
+        if id == 0 {
+            return Err(Error::Load {
+                kind: ResourceKind::Shader,
+                path: PathBuf::from(path),
+                message: messages.pop(),
+            });
+        }
+
+        Ok(Self {
+            id,
+            locations: RefCell::default(),
+            _window: PhantomData,
+        })
+    }
+
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Returns the location of a uniform, looking it up on first use.
+    pub fn location(&self, name: &str) -> Result<i32> {
+        if let Some(&location) = self.locations.borrow().get(name) {
+            return location_result(name, location);
+        }
+
+        let c_name = CString::new(name).map_err(|_| Error::UniformNotFound(name.to_owned()))?;
+        let location = unsafe { sys::rlGetLocationUniform(self.id, c_name.as_ptr()) };
+        self.locations
+            .borrow_mut()
+            .insert(name.to_owned(), location);
+
+        location_result(name, location)
+    }
+
+    /// Sets a uniform, or an array uniform when `values` has more than one
+    /// element.
+    pub fn set<T: UniformElement>(&self, name: &str, values: &[T]) -> Result<()> {
+        let location = self.location(name)?;
+
+        unsafe {
+            sys::rlEnableShader(self.id);
+            sys::rlSetUniform(
+                location,
+                values.as_ptr() as *const c_void,
+                T::DATA_TYPE as c_int,
+                values.len() as c_int,
+            );
+            sys::rlDisableShader();
+        }
+
+        Ok(())
+    }
+
+    /// Runs the shader over a grid of work groups.
+    pub fn dispatch(&self, groups_x: u32, groups_y: u32, groups_z: u32) {
+        unsafe {
+            sys::rlEnableShader(self.id);
+            sys::rlComputeShaderDispatch(groups_x, groups_y, groups_z);
+            sys::rlDisableShader();
+        }
+    }
+}
+
+impl Drop for ComputeShader<'_> {
+    fn drop(&mut self) {
+        unsafe { sys::rlUnloadShaderProgram(self.id) }
+    }
+}
+
+fn location_result(name: &str, location: i32) -> Result<i32> {
+    if location < 0 {
+        Err(Error::UniformNotFound(name.to_owned()))
+    } else {
+        Ok(location)
+    }
+}
+
+/// A shader storage buffer holding `len` elements of `T`, unloaded when
+/// dropped. It borrows the [`Window`] it was created with.
+#[derive(Debug)]
+pub struct ShaderBuffer<'w, T: Pod> {
+    id: u32,
+    len: usize,
+    _marker: PhantomData<(T, &'w Window)>,
+}
+
+impl<'w, T: Pod> ShaderBuffer<'w, T> {
+    /// Creates a zero-filled buffer.
+    pub fn new(window: &'w Window, len: usize, usage: BufferUsage) -> Result<Self> {
+        Self::create(window, len, std::ptr::null(), usage)
+    }
+
+    /// Creates a buffer initialised with `data`.
+    pub fn from_slice(window: &'w Window, data: &[T], usage: BufferUsage) -> Result<Self> {
+        Self::create(window, data.len(), data.as_ptr() as *const c_void, usage)
+    }
+
+    /// Fails if rlgl could not create the buffer, for example because the
+    /// driver has no shader storage buffer support.
+    fn create(
+        _window: &'w Window,
+        len: usize,
+        data: *const c_void,
+        usage: BufferUsage,
+    ) -> Result<Self> {
+        let size = byte_len::<T>(len);
+        let id = error::load(
+            ResourceKind::ShaderBuffer,
+            Path::new("<memory>"),
+            |_| unsafe { sys::rlLoadShaderBuffer(size, data, usage.to_gl()) },
+            |&id| id != 0,
+        )?;
+
+        Ok(Self {
+            id,
+            len,
+            _marker: PhantomData,
+        })
+    }
+
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Writes `data` starting at element `offset`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the write would run past the end of the buffer.
+    pub fn write(&mut self, offset: usize, data: &[T]) {
+        check_range(offset, data.len(), self.len);
+
+        unsafe {
+            sys::rlUpdateShaderBuffer(
+                self.id,
+                data.as_ptr() as *const c_void,
+                byte_len::<T>(data.len()),
+                byte_len::<T>(offset),
+            )
+        }
+    }
+
+    /// Reads elements starting at `offset` into `out`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the read would run past the end of the buffer.
+    pub fn read(&self, offset: usize, out: &mut [T]) {
+        check_range(offset, out.len(), self.len);
+
+        unsafe {
+            sys::rlReadShaderBuffer(
+                self.id,
+                out.as_mut_ptr() as *mut c_void,
+                byte_len::<T>(out.len()),
+                byte_len::<T>(offset),
+            )
+        }
+    }
+
+    /// Reads the whole buffer back into CPU memory.
+    pub fn to_vec(&self) -> Vec<T> {
+        let mut out = vec![T::zeroed(); self.len];
+        self.read(0, &mut out);
+        out
+    }
+
+    /// Copies `count` elements from `src` on the GPU.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either range runs past the end of its buffer.
+    pub fn copy_from(
+        &mut self,
+        src: &ShaderBuffer<'_, T>,
+        src_offset: usize,
+        dst_offset: usize,
+        count: usize,
+    ) {
+        check_range(src_offset, count, src.len);
+        check_range(dst_offset, count, self.len);
+
+        unsafe {
+            sys::rlCopyShaderBuffer(
+                self.id,
+                src.id,
+                byte_len::<T>(dst_offset),
+                byte_len::<T>(src_offset),
+                byte_len::<T>(count),
+            )
+        }
+    }
+
+    /// Binds the buffer to a shader storage binding point.
+    pub fn bind(&self, index: u32) {
+        unsafe { sys::rlBindShaderBuffer(self.id, index) }
+    }
+}
+
+impl<T: Pod> Drop for ShaderBuffer<'_, T> {
+    fn drop(&mut self) {
+        unsafe { sys::rlUnloadShaderBuffer(self.id) }
+    }
+}
+
+/// Binds a texture to an image unit for `imageLoad`/`imageStore` access.
+pub fn bind_image_texture(texture: &OwnedTexture, index: u32, access: ImageAccess) {
+    let raw = texture.as_raw();
+
+    unsafe { sys::rlBindImageTexture(raw.id, index, raw.format, access == ImageAccess::ReadOnly) }
+}
+
+fn byte_len<T>(len: usize) -> u32 {
+    len.checked_mul(size_of::<T>())
+        .and_then(|bytes| u32::try_from(bytes).ok())
+        .expect("shader buffer size exceeds u32::MAX bytes")
+}
+
+fn check_range(offset: usize, count: usize, len: usize) {
+    assert!(
+        offset.checked_add(count).is_some_and(|end| end <= len),
+        "range {offset}..{offset}+{count} out of bounds for shader buffer of length {len}"
+    );
+}
@@ -0,0 +1,231 @@
+use crate::error::{self, ResourceKind, Result};
+use crate::font::OwnedFont;
+use crate::image::OwnedImage;
+use crate::sys;
+use crate::sys::{Color, Rectangle, Vector2};
+use crate::texture::{OwnedRenderTexture, OwnedTexture};
+use crate::window::Window;
+use std::ffi::CString;
+use std::os::raw::c_int;
+use std::path::Path;
+
+/// A surface that shapes and text can be drawn onto.
+///
+/// Implemented by [`DrawHandle`] for the screen, [`TextureHandle`] for render
+/// textures, and [`ImageCanvas`](crate::image::ImageCanvas) for CPU-side
+/// images, so the same drawing code can target any of them.
+pub trait Canvas {
+    /// Fills the whole surface with `color`.
+    fn clear(&mut self, color: Color);
+
+    fn draw_pixel(&mut self, position: Vector2, color: Color);
+
+    fn draw_line(&mut self, start: Vector2, end: Vector2, thickness: f32, color: Color);
+
+    fn draw_circle(&mut self, center: Vector2, radius: f32, color: Color);
+
+    fn draw_circle_lines(&mut self, center: Vector2, radius: f32, color: Color);
+
+    fn draw_rectangle(&mut self, rect: Rectangle, color: Color);
+
+    fn draw_rectangle_lines(&mut self, rect: Rectangle, thickness: f32, color: Color);
+
+    /// Draws a filled triangle. Vertices must be in counter-clockwise order.
+    fn draw_triangle(&mut self, v1: Vector2, v2: Vector2, v3: Vector2, color: Color);
+
+    fn draw_triangle_lines(&mut self, v1: Vector2, v2: Vector2, v3: Vector2, color: Color);
+
+    /// Draws a triangle fan around `points[0]`. Points must be in
+    /// counter-clockwise order.
+    fn draw_triangle_fan(&mut self, points: &[Vector2], color: Color);
+
+    /// Draws text with its top-left corner at `position`.
+    ///
+    /// Text stops at the first NUL character.
+    fn draw_text(
+        &mut self,
+        font: &OwnedFont,
+        text: &str,
+        position: Vector2,
+        font_size: f32,
+        spacing: f32,
+        tint: Color,
+    );
+}
+
+/// An in-progress frame on the window, ended and presented when dropped.
+///
+/// Created by [`Window::begin_drawing`].
+#[derive(Debug)]
+pub struct DrawHandle<'w> {
+    window: &'w Window,
+}
+
+impl<'w> DrawHandle<'w> {
+    pub(crate) fn new(window: &'w Window) -> Self {
+        unsafe { sys::BeginDrawing() }
+        Self { window }
+    }
+
+    /// The window being drawn to.
+    pub fn window(&self) -> &'w Window {
+        self.window
+    }
+
+    /// Reads the framebuffer back into an image, at the render size,
+    /// including everything drawn so far this frame.
+    ///
+    /// This lives on the draw handle because once a frame is presented the
+    /// framebuffer's contents are undefined.
+    pub fn screenshot(&self) -> Result<OwnedImage> {
+        error::load(
+            ResourceKind::Image,
+            Path::new("<screen>"),
+            |_| unsafe {
+                // Flush batched draws so they are in the framebuffer.
+                sys::rlDrawRenderBatchActive();
+                OwnedImage::from_raw(sys::LoadImageFromScreen())
+            },
+            |image| unsafe { sys::IsImageValid(*image.as_raw()) },
+        )
+    }
+
+    /// Draws the `source` region of a texture into `dest`, rotated by
+    /// `rotation` degrees around `origin` (relative to `dest`).
+    pub fn draw_texture(
+        &mut self,
+        texture: &OwnedTexture,
+        source: Rectangle,
+        dest: Rectangle,
+        origin: Vector2,
+        rotation: f32,
+        tint: Color,
+    ) {
+        unsafe { sys::DrawTexturePro(*texture.as_raw(), source, dest, origin, rotation, tint) }
+    }
+
+    /// Draws the current frame rate in the corner at `(x, y)`.
+    pub fn draw_fps(&mut self, x: i32, y: i32) {
+        unsafe { sys::DrawFPS(x, y) }
+    }
+}
+
+/// Implements [`Canvas`] with raylib's immediate-mode drawing functions,
+/// which draw to whichever framebuffer is bound.
+macro_rules! gl_canvas {
+    ($ty:ty) => {
+        impl Canvas for $ty {
+            fn clear(&mut self, color: Color) {
+                unsafe { sys::ClearBackground(color) }
+            }
+
+            fn draw_pixel(&mut self, position: Vector2, color: Color) {
+                unsafe { sys::DrawPixelV(position, color) }
+            }
+
+            fn draw_line(&mut self, start: Vector2, end: Vector2, thickness: f32, color: Color) {
+                unsafe { sys::DrawLineEx(start, end, thickness, color) }
+            }
+
+            fn draw_circle(&mut self, center: Vector2, radius: f32, color: Color) {
+                unsafe { sys::DrawCircleV(center, radius, color) }
+            }
+
+            fn draw_circle_lines(&mut self, center: Vector2, radius: f32, color: Color) {
+                unsafe { sys::DrawCircleLinesV(center, radius, color) }
+            }
+
+            fn draw_rectangle(&mut self, rect: Rectangle, color: Color) {
+                unsafe { sys::DrawRectangleRec(rect, color) }
+            }
+
+            fn draw_rectangle_lines(&mut self, rect: Rectangle, thickness: f32, color: Color) {
+                unsafe { sys::DrawRectangleLinesEx(rect, thickness, color) }
+            }
+
+            fn draw_triangle(&mut self, v1: Vector2, v2: Vector2, v3: Vector2, color: Color) {
+                unsafe { sys::DrawTriangle(v1, v2, v3, color) }
+            }
+
+            fn draw_triangle_lines(&mut self, v1: Vector2, v2: Vector2, v3: Vector2, color: Color) {
+                unsafe { sys::DrawTriangleLines(v1, v2, v3, color) }
+            }
+
+            fn draw_triangle_fan(&mut self, points: &[Vector2], color: Color) {
+                unsafe { sys::DrawTriangleFan(points.as_ptr(), points.len() as c_int, color) }
+            }
+
+            fn draw_text(
+                &mut self,
+                font: &OwnedFont,
+                text: &str,
+                position: Vector2,
+                font_size: f32,
+                spacing: f32,
+                tint: Color,
+            ) {
+                let text = text_cstring(text);
+                unsafe {
+                    sys::DrawTextEx(
+                        *font.as_raw(),
+                        text.as_ptr(),
+                        position,
+                        font_size,
+                        spacing,
+                        tint,
+                    )
+                }
+            }
+        }
+    };
+}
+
+gl_canvas!(DrawHandle<'_>);
+gl_canvas!(TextureHandle<'_, '_>);
+
+impl Drop for DrawHandle<'_> {
+    fn drop(&mut self) {
+        unsafe { sys::EndDrawing() }
+    }
+}
+
+/// An in-progress pass drawing into a render texture, ended when dropped.
+///
+/// Created by [`OwnedRenderTexture::begin_drawing`].
+#[derive(Debug)]
+pub struct TextureHandle<'t, 'w> {
+    _target: &'t mut OwnedRenderTexture<'w>,
+}
+
+impl<'t, 'w> TextureHandle<'t, 'w> {
+    pub(crate) fn new(target: &'t mut OwnedRenderTexture<'w>) -> Self {
+        unsafe { sys::BeginTextureMode(*target.as_raw()) }
+        Self { _target: target }
+    }
+
+    /// Draws the `source` region of a texture into `dest`, rotated by
+    /// `rotation` degrees around `origin` (relative to `dest`).
+    pub fn draw_texture(
+        &mut self,
+        texture: &OwnedTexture,
+        source: Rectangle,
+        dest: Rectangle,
+        origin: Vector2,
+        rotation: f32,
+        tint: Color,
+    ) {
+        unsafe { sys::DrawTexturePro(*texture.as_raw(), source, dest, origin, rotation, tint) }
+    }
+}
+
+impl Drop for TextureHandle<'_, '_> {
+    fn drop(&mut self) {
+        unsafe { sys::EndTextureMode() }
+    }
+}
+
+/// Converts text for raylib, cutting it off at the first NUL.
+pub(crate) fn text_cstring(text: &str) -> CString {
+    let end = text.find('\0').unwrap_or(text.len());
+    CString::new(&text[..end]).unwrap_or_default()
+}
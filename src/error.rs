@@ -0,0 +1,196 @@
+use crate::log;
+use crate::sys::PixelFormat;
+use std::ffi::CStr;
+use std::fmt::{Display, Formatter};
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// The kinds of resource raylib can load.
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum ResourceKind {
+    Texture,
+    Shader,
+    Font,
+    Model,
+    Sound,
+    Music,
+    Wave,
+    Image,
+    RenderTexture,
+    ShaderBuffer,
+}
+
+impl Display for ResourceKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::Texture => "texture",
+            Self::Shader => "shader",
+            Self::Font => "font",
+            Self::Model => "model",
+            Self::Sound => "sound",
+            Self::Music => "music stream",
+            Self::Wave => "wave",
+            Self::Image => "image",
+            Self::RenderTexture => "render texture",
+            Self::ShaderBuffer => "shader storage buffer",
+        };
+
+        f.write_str(name)
+    }
+}
+
+/// The error type returned by the safe API.
+///
+/// To fill in the `message` of a failed load, export, or window, rraylib
+/// installs its own trace-log callback the first time it calls into one of
+/// them. The callback prints messages like raylib's default logger, but it
+/// replaces any callback set earlier with [`sys::SetTraceLogCallback`]. A
+/// callback set afterwards is left in place, in which case `message` is
+/// always `None`.
+///
+/// [`sys::SetTraceLogCallback`]: crate::sys::SetTraceLogCallback
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// raylib could not load a resource.
+    Load {
+        kind: ResourceKind,
+        path: PathBuf,
+        /// The last warning or error raylib logged while loading, if any.
+        message: Option<String>,
+    },
+    /// raylib could not write a resource to a file.
+    Export {
+        path: PathBuf,
+        /// The last warning or error raylib logged while exporting, if any.
+        message: Option<String>,
+    },
+    /// The window could not be opened.
+    InitWindow {
+        /// The last warning or error raylib logged while opening it, if any.
+        message: Option<String>,
+    },
+    /// Shader source could not be preprocessed.
+    Preprocess {
+        path: PathBuf,
+        line: usize,
+        message: String,
+    },
+    /// An image's pixel format is not supported by the operation.
+    UnsupportedFormat(PixelFormat),
+    /// A string is not a valid hex color.
+    ParseColor { input: String },
+    /// A palette file's extension is not one of the supported formats.
+    UnsupportedPaletteFormat(PathBuf),
+    /// A palette file could not be parsed.
+    ParsePalette {
+        path: PathBuf,
+        line: usize,
+        message: String,
+    },
+    /// A shader has no active uniform with the given name.
+    UniformNotFound(String),
+    /// A path or string could not be handed to raylib.
+    Io(io::Error),
+}
+
+/// A specialised `Result` type for the safe API.
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Load {
+                kind,
+                path,
+                message,
+            } => {
+                write!(f, "failed to load {kind} '{}'", path.display())?;
+
+                if let Some(message) = message {
+                    write!(f, ": {message}")?;
+                }
+
+                Ok(())
+            }
+            Self::Export { path, message } => {
+                write!(f, "failed to export '{}'", path.display())?;
+
+                if let Some(message) = message {
+                    write!(f, ": {message}")?;
+                }
+
+                Ok(())
+            }
+            Self::InitWindow { message } => {
+                f.write_str("failed to open the window")?;
+
+                if let Some(message) = message {
+                    write!(f, ": {message}")?;
+                }
+
+                Ok(())
+            }
+            Self::Preprocess {
+                path,
+                line,
+                message,
+            }
+            | Self::ParsePalette {
+                path,
+                line,
+                message,
+            } => write!(f, "{}:{line}: {message}", path.display()),
+            Self::UnsupportedFormat(format) => write!(f, "unsupported pixel format {format:?}"),
+            Self::ParseColor { input } => write!(f, "invalid hex color '{input}'"),
+            Self::UnsupportedPaletteFormat(path) => {
+                write!(f, "unsupported palette format '{}'", path.display())
+            }
+            Self::UniformNotFound(name) => write!(f, "shader uniform not found: '{name}'"),
+            Self::Io(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(value: io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+/// Calls a raylib loader on `path` and turns an invalid result into an
+/// [`Error::Load`] carrying the last message raylib logged.
+///
+/// `load` should wrap the raw value in its owning type so that anything
+/// raylib allocated for a partially loaded resource is released on failure.
+pub(crate) fn load<T>(
+    kind: ResourceKind,
+    path: &Path,
+    load: impl FnOnce(&CStr) -> T,
+    is_valid: impl FnOnce(&T) -> bool,
+) -> Result<T> {
+    let c_path = crate::fs::to_cstring(path)?;
+    let (value, mut messages) = log::capture(|| {
+        let value = load(&c_path);
+        let valid = is_valid(&value);
+        (value, valid)
+    });
+
+    match value {
+        (value, true) => Ok(value),
+        (_, false) => Err(Error::Load {
+            kind,
+            path: path.to_path_buf(),
+            message: messages.pop(),
+        }),
+    }
+}
@@ -0,0 +1,180 @@
+use crate::error::{self, Error, ResourceKind, Result};
+use crate::image::OwnedImage;
+use crate::sys;
+use crate::sys::{FontType, PixelFormat};
+use std::io;
+use std::mem::ManuallyDrop;
+use std::os::raw::c_int;
+use std::path::Path;
+use std::slice;
+
+/// The number of glyphs `LoadFontData` rasterizes when given no codepoints:
+/// printable ASCII, from `' '` to `'~'`.
+const ASCII_GLYPHS: usize = 95;
+
+/// A font and its glyph atlas, unloaded when dropped.
+#[derive(Debug)]
+pub struct OwnedFont {
+    raw: sys::Font,
+}
+
+impl OwnedFont {
+    /// Loads a font from a file.
+    ///
+    /// raylib substitutes its default font when loading fails; this is
+    /// reported as an error rather than handed back silently.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        error::load(
+            ResourceKind::Font,
+            path.as_ref(),
+            |path| Self {
+                raw: unsafe { sys::LoadFont(path.as_ptr()) },
+            },
+            |font| unsafe {
+                sys::IsFontValid(font.raw)
+                    && font.raw.texture.id != sys::GetFontDefault().texture.id
+            },
+        )
+    }
+
+    /// Takes ownership of a raw font.
+    ///
+    /// # Safety
+    ///
+    /// `raw` must be a valid font that is not unloaded anywhere else.
+    pub unsafe fn from_raw(raw: sys::Font) -> Self {
+        Self { raw }
+    }
+
+    /// Releases ownership of the font without unloading it.
+    pub fn into_raw(self) -> sys::Font {
+        ManuallyDrop::new(self).raw
+    }
+
+    pub fn as_raw(&self) -> &sys::Font {
+        &self.raw
+    }
+
+    pub fn base_size(&self) -> i32 {
+        self.raw.baseSize
+    }
+}
+
+impl Drop for OwnedFont {
+    fn drop(&mut self) {
+        // UnloadFont leaves the default font alone, so this is safe even when
+        // raylib substituted it for a failed load.
+        unsafe { sys::UnloadFont(self.raw) }
+    }
+}
+
+/// A TrueType or OpenType font rasterized into CPU-side glyph images, for
+/// drawing text onto images without a window.
+///
+/// Unlike [`OwnedFont`], which needs an OpenGL context for its atlas, this
+/// holds only the glyphs raylib rasterizes on the CPU. It covers printable
+/// ASCII; other characters are drawn as `'?'`.
+#[derive(Debug)]
+pub struct ImageFont {
+    glyphs: *mut sys::GlyphInfo,
+    size: i32,
+}
+
+impl ImageFont {
+    /// Loads a TrueType or OpenType font from a file, rasterized at `size`
+    /// pixels.
+    pub fn load(path: impl AsRef<Path>, size: i32) -> Result<Self> {
+        let path = path.as_ref();
+        let data = crate::fs::load_file_data(ResourceKind::Font, path)?;
+        Self::decode(path, &data, size)
+    }
+
+    /// Loads a TrueType or OpenType font from file data in memory,
+    /// rasterized at `size` pixels.
+    pub fn load_from_memory(data: &[u8], size: i32) -> Result<Self> {
+        Self::decode(Path::new("<memory>"), data, size)
+    }
+
+    fn decode(path: &Path, data: &[u8], size: i32) -> Result<Self> {
+        let data_size = c_int::try_from(data.len())
+            .map_err(|e| Error::Io(io::Error::new(io::ErrorKind::InvalidInput, e)))?;
+
+        let mut font = error::load(
+            ResourceKind::Font,
+            path,
+            |_| Self {
+                glyphs: unsafe {
+                    sys::LoadFontData(
+                        data.as_ptr(),
+                        data_size,
+                        size,
+                        std::ptr::null_mut(),
+                        0,
+                        FontType::FONT_DEFAULT as c_int,
+                    )
+                },
+                size,
+            },
+            |font| !font.glyphs.is_null(),
+        )?;
+
+        font.add_alpha();
+        Ok(font)
+    }
+
+    /// The size in pixels the glyphs were rasterized at.
+    pub fn size(&self) -> i32 {
+        self.size
+    }
+
+    /// The glyph for `c`, falling back to `'?'`.
+    pub(crate) fn glyph(&self, c: char) -> &sys::GlyphInfo {
+        let glyphs = self.glyphs();
+
+        glyphs
+            .iter()
+            .find(|g| g.value == c as c_int)
+            .or_else(|| glyphs.iter().find(|g| g.value == '?' as c_int))
+            .unwrap_or(&glyphs[0])
+    }
+
+    fn glyphs(&self) -> &[sys::GlyphInfo] {
+        unsafe { slice::from_raw_parts(self.glyphs, ASCII_GLYPHS) }
+    }
+
+    /// Replaces the grayscale coverage raylib rasterizes with white glyphs
+    /// whose alpha is the coverage, so they can be tinted and blended.
+    fn add_alpha(&mut self) {
+        let glyphs = unsafe { slice::from_raw_parts_mut(self.glyphs, ASCII_GLYPHS) };
+
+        for glyph in glyphs {
+            let image = &mut glyph.image;
+            let len = image.width.max(0) as usize * image.height.max(0) as usize;
+
+            if image.format != PixelFormat::PIXELFORMAT_UNCOMPRESSED_GRAYSCALE as c_int
+                || image.data.is_null()
+                || len == 0
+            {
+                continue;
+            }
+
+            let coverage = unsafe { slice::from_raw_parts(image.data as *const u8, len) };
+            let pixels: Vec<u8> = coverage.iter().flat_map(|&a| [255, a]).collect();
+            let replacement = OwnedImage::from_bytes(
+                image.width,
+                image.height,
+                PixelFormat::PIXELFORMAT_UNCOMPRESSED_GRAY_ALPHA,
+                &pixels,
+            );
+
+            unsafe { sys::UnloadImage(*image) };
+            *image = replacement.into_raw();
+        }
+    }
+}
+
+impl Drop for ImageFont {
+    fn drop(&mut self) {
+        unsafe { sys::UnloadFontData(self.glyphs, ASCII_GLYPHS as c_int) }
+    }
+}
@@ -0,0 +1,333 @@
+use crate::error::{Error, ResourceKind, Result};
+use crate::log;
+use crate::sys;
+use std::ffi::{CStr, CString};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// The filter tag raylib uses to include directories in a listing.
+pub const DIRECTORY_FILTER: &str = "DIR";
+
+/// Lists the entries of a directory, optionally filtered and recursive.
+///
+/// The filter uses raylib's syntax: a `;`-separated list of extensions
+/// (e.g. `".png;.jpg"`), optionally including [`DIRECTORY_FILTER`] to
+/// also yield directories.
+#[derive(Debug, Clone)]
+pub struct DirectoryListing {
+    path: PathBuf,
+    filter: Option<String>,
+    recursive: bool,
+}
+
+impl DirectoryListing {
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            filter: None,
+            recursive: false,
+        }
+    }
+
+    /// Only yields entries matching the given raylib filter string.
+    pub fn filter(mut self, filter: impl Into<String>) -> Self {
+        self.filter = Some(filter.into());
+        self
+    }
+
+    /// Descends into subdirectories when `true`.
+    pub fn recursive(mut self, recursive: bool) -> Self {
+        self.recursive = recursive;
+        self
+    }
+
+    /// Scans the directory and returns an iterator over the matching paths.
+    pub fn iter(&self) -> io::Result<DirectoryFiles> {
+        let path = to_cstring(&self.path)?;
+
+        if !unsafe { sys::DirectoryExists(path.as_ptr()) } {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("directory not found: {}", self.path.display()),
+            ));
+        }
+
+        let filter = self
+            .filter
+            .as_deref()
+            .map(CString::new)
+            .transpose()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+        let list = unsafe {
+            if self.filter.is_none() && !self.recursive {
+                sys::LoadDirectoryFiles(path.as_ptr())
+            } else {
+                sys::LoadDirectoryFilesEx(
+                    path.as_ptr(),
+                    filter.as_ref().map_or(std::ptr::null(), |f| f.as_ptr()),
+                    self.recursive,
+                )
+            }
+        };
+
+        let paths = unsafe { copy_path_list(&list) };
+        unsafe { sys::UnloadDirectoryFiles(list) };
+
+        Ok(DirectoryFiles {
+            inner: paths.into_iter(),
+        })
+    }
+}
+
+/// An iterator over the paths produced by a [`DirectoryListing`].
+#[derive(Debug)]
+pub struct DirectoryFiles {
+    inner: std::vec::IntoIter<PathBuf>,
+}
+
+impl Iterator for DirectoryFiles {
+    type Item = PathBuf;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for DirectoryFiles {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back()
+    }
+}
+
+impl ExactSizeIterator for DirectoryFiles {}
+
+/// Lists the immediate entries (files and directories) of a directory.
+pub fn read_dir(path: impl AsRef<Path>) -> io::Result<DirectoryFiles> {
+    DirectoryListing::new(path).iter()
+}
+
+/// Recursively lists the files below a directory that match a raylib filter.
+pub fn walk_dir(path: impl AsRef<Path>, filter: &str) -> io::Result<DirectoryFiles> {
+    DirectoryListing::new(path)
+        .filter(filter)
+        .recursive(true)
+        .iter()
+}
+
+/// Returns `true` if the file exists.
+pub fn file_exists(path: impl AsRef<Path>) -> io::Result<bool> {
+    let path = to_cstring(path.as_ref())?;
+    Ok(unsafe { sys::FileExists(path.as_ptr()) })
+}
+
+/// Returns the last modification time of a file.
+pub fn file_mod_time(path: impl AsRef<Path>) -> io::Result<SystemTime> {
+    let path = path.as_ref();
+    let c_path = existing_file(path)?;
+    let secs = unsafe { sys::GetFileModTime(c_path.as_ptr()) };
+
+    if secs <= 0 {
+        return Err(io::Error::other(format!(
+            "failed to read modification time: {}",
+            path.display()
+        )));
+    }
+
+    Ok(UNIX_EPOCH + Duration::from_secs(secs as u64))
+}
+
+/// Returns the length of a file in bytes.
+///
+/// This reads the file's metadata rather than calling `GetFileLength`,
+/// whose `int` result can't represent files of 2 GiB or more.
+pub fn file_length(path: impl AsRef<Path>) -> io::Result<u64> {
+    Ok(std::fs::metadata(path)?.len())
+}
+
+/// Reads a whole file with `LoadFileData`, so raylib's file callbacks apply,
+/// reporting failure as an [`Error::Load`] of `kind`.
+pub(crate) fn load_file_data(kind: ResourceKind, path: &Path) -> Result<Vec<u8>> {
+    let c_path = to_cstring(path)?;
+
+    let (data, mut messages) = log::capture(|| unsafe {
+        let mut size = 0;
+        let data = sys::LoadFileData(c_path.as_ptr(), &mut size);
+
+        if data.is_null() {
+            return None;
+        }
+
+        let bytes = std::slice::from_raw_parts(data, size.max(0) as usize).to_vec();
+        sys::UnloadFileData(data);
+        Some(bytes)
+    });
+
+    data.ok_or_else(|| Error::Load {
+        kind,
+        path: path.to_path_buf(),
+        message: messages.pop(),
+    })
+}
+
+fn existing_file(path: &Path) -> io::Result<CString> {
+    let c_path = to_cstring(path)?;
+
+    if !unsafe { sys::FileExists(c_path.as_ptr()) } {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("file not found: {}", path.display()),
+        ));
+    }
+
+    Ok(c_path)
+}
+
+unsafe fn copy_path_list(list: &sys::FilePathList) -> Vec<PathBuf> {
+    if list.paths.is_null() {
+        return Vec::new();
+    }
+
+    std::slice::from_raw_parts(list.paths, list.count as usize)
+        .iter()
+        .map(|&p| from_cstr(CStr::from_ptr(p)))
+        .collect()
+}
+
+/// Converts a path to a nul-terminated string suitable for raylib.
+pub(crate) fn to_cstring(path: &Path) -> io::Result<CString> {
+    #[cfg(unix)]
+    let bytes = {
+        use std::os::unix::ffi::OsStrExt;
+        path.as_os_str().as_bytes().to_vec()
+    };
+
+    #[cfg(not(unix))]
+    let bytes = path
+        .to_str()
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("path is not valid UTF-8: {}", path.display()),
+            )
+        })?
+        .as_bytes()
+        .to_vec();
+
+    CString::new(bytes).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
+}
+
+fn from_cstr(s: &CStr) -> PathBuf {
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStrExt;
+        PathBuf::from(std::ffi::OsStr::from_bytes(s.to_bytes()))
+    }
+
+    #[cfg(not(unix))]
+    {
+        PathBuf::from(s.to_string_lossy().into_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    /// Creates an empty directory unique to this process and `name`.
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("rraylib-fs-{}-{name}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn file_length_matches_contents() {
+        let dir = scratch_dir("length");
+        let path = dir.join("data.bin");
+        fs::write(&path, [0u8; 1234]).unwrap();
+
+        assert_eq!(file_length(&path).unwrap(), 1234);
+        assert_eq!(
+            file_length(dir.join("missing.bin")).unwrap_err().kind(),
+            io::ErrorKind::NotFound
+        );
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn file_exists_and_mod_time() {
+        let dir = scratch_dir("exists");
+        let path = dir.join("file.txt");
+        fs::write(&path, "hello").unwrap();
+
+        assert!(file_exists(&path).unwrap());
+        assert!(!file_exists(dir.join("missing.txt")).unwrap());
+
+        let modified = file_mod_time(&path).unwrap();
+        let expected = fs::metadata(&path).unwrap().modified().unwrap();
+        let difference = expected
+            .duration_since(modified)
+            .unwrap_or_else(|e| e.duration());
+        assert!(difference < Duration::from_secs(2));
+
+        assert_eq!(
+            file_mod_time(dir.join("missing.txt")).unwrap_err().kind(),
+            io::ErrorKind::NotFound
+        );
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn listings() {
+        let dir = scratch_dir("listing");
+        fs::create_dir(dir.join("sub")).unwrap();
+        fs::write(dir.join("a.png"), "").unwrap();
+        fs::write(dir.join("b.txt"), "").unwrap();
+        fs::write(dir.join("sub").join("c.png"), "").unwrap();
+
+        let names = |files: DirectoryFiles| {
+            let mut names: Vec<_> = files
+                .map(|p| p.file_name().unwrap().to_string_lossy().into_owned())
+                .collect();
+            names.sort();
+            names
+        };
+
+        assert_eq!(names(read_dir(&dir).unwrap()), ["a.png", "b.txt", "sub"]);
+        assert_eq!(names(walk_dir(&dir, ".png").unwrap()), ["a.png", "c.png"]);
+        assert_eq!(
+            names(
+                DirectoryListing::new(&dir)
+                    .filter(format!(".txt;{DIRECTORY_FILTER}"))
+                    .iter()
+                    .unwrap()
+            ),
+            ["b.txt", "sub"]
+        );
+
+        assert_eq!(
+            read_dir(dir.join("missing")).unwrap_err().kind(),
+            io::ErrorKind::NotFound
+        );
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn paths_with_nul_are_rejected() {
+        assert_eq!(
+            to_cstring(Path::new("a\0b")).unwrap_err().kind(),
+            io::ErrorKind::InvalidInput
+        );
+    }
+}
@@ -0,0 +1,521 @@
+use crate::error::{self, Error, ResourceKind, Result};
+use crate::log;
+use crate::sys;
+use crate::sys::{Color, PixelFormat, Vector3, Vector4};
+use std::ffi::CString;
+use std::io;
+use std::mem::ManuallyDrop;
+use std::os::raw::{c_int, c_uint};
+use std::path::Path;
+use std::ptr;
+use std::slice;
+
+mod animated;
+mod canvas;
+#[cfg(feature = "image")]
+mod interop;
+mod process;
+
+/// Procedural images from raylib's generators, plus composable noise
+/// sources sampled into a [`Field`](generate::Field) and colored with a
+/// [`GradientMap`](generate::GradientMap)
+pub mod generate;
+
+pub use animated::*;
+pub use canvas::*;
+
+/// A type that exactly matches the layout of one pixel in an uncompressed
+/// [`PixelFormat`].
+///
+/// # Safety
+///
+/// `Self` must have the size of one pixel in `FORMAT`, an alignment no
+/// greater than 16 (the alignment of raylib's allocations), and every bit
+/// pattern must be a valid value.
+pub unsafe trait Pixel: Copy {
+    const FORMAT: PixelFormat;
+}
+
+macro_rules! pixel {
+    ($($ty:ty => $format:ident),* $(,)?) => {
+        $(
+            unsafe impl Pixel for $ty {
+                const FORMAT: PixelFormat = PixelFormat::$format;
+            }
+        )*
+    };
+}
+
+pixel! {
+    u8 => PIXELFORMAT_UNCOMPRESSED_GRAYSCALE,
+    [u8; 2] => PIXELFORMAT_UNCOMPRESSED_GRAY_ALPHA,
+    [u8; 3] => PIXELFORMAT_UNCOMPRESSED_R8G8B8,
+    [u8; 4] => PIXELFORMAT_UNCOMPRESSED_R8G8B8A8,
+    Color => PIXELFORMAT_UNCOMPRESSED_R8G8B8A8,
+    f32 => PIXELFORMAT_UNCOMPRESSED_R32,
+    [f32; 3] => PIXELFORMAT_UNCOMPRESSED_R32G32B32,
+    Vector3 => PIXELFORMAT_UNCOMPRESSED_R32G32B32,
+    [f32; 4] => PIXELFORMAT_UNCOMPRESSED_R32G32B32A32,
+    Vector4 => PIXELFORMAT_UNCOMPRESSED_R32G32B32A32,
+}
+
+/// A CPU-side image that is unloaded when dropped.
+///
+/// Images live entirely in main memory, so none of their methods need an
+/// open window.
+#[derive(Debug)]
+pub struct OwnedImage {
+    raw: sys::Image,
+}
+
+impl OwnedImage {
+    /// Loads an image from a file.
+    ///
+    /// With the `image` feature, files raylib can't decode are decoded by the
+    /// `image` crate instead.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let result = error::load(
+            ResourceKind::Image,
+            path,
+            |path| Self {
+                raw: unsafe { sys::LoadImage(path.as_ptr()) },
+            },
+            |image| unsafe { sys::IsImageValid(image.raw) },
+        );
+
+        #[cfg(feature = "image")]
+        let result = result.or_else(|e| interop::decode_file(path).ok_or(e));
+
+        result
+    }
+
+    /// Decodes an image from file data in memory. `file_type` is the file
+    /// extension, such as `".png"`.
+    ///
+    /// With the `image` feature, data raylib can't decode is decoded by the
+    /// `image` crate instead, detecting the format from its contents.
+    pub fn load_from_memory(file_type: &str, data: &[u8]) -> Result<Self> {
+        let file_type = CString::new(file_type)
+            .map_err(|e| Error::Io(io::Error::new(io::ErrorKind::InvalidInput, e)))?;
+        let size = c_int::try_from(data.len())
+            .map_err(|e| Error::Io(io::Error::new(io::ErrorKind::InvalidInput, e)))?;
+
+        let result = error::load(
+            ResourceKind::Image,
+            Path::new("<memory>"),
+            |_| unsafe {
+                Self::from_raw(sys::LoadImageFromMemory(
+                    file_type.as_ptr(),
+                    data.as_ptr(),
+                    size,
+                ))
+            },
+            |image| unsafe { sys::IsImageValid(image.raw) },
+        );
+
+        #[cfg(feature = "image")]
+        let result = result.or_else(|e| interop::decode(data).ok_or(e));
+
+        result
+    }
+
+    /// Creates an image filled with a single color, in R8G8B8A8 format.
+    pub fn new(width: i32, height: i32, color: Color) -> Self {
+        unsafe { Self::from_raw(sys::GenImageColor(width, height, color)) }
+    }
+
+    /// Creates an image from pixel data in the given format, without
+    /// mipmaps.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `data` is not exactly the size of a `width` by `height`
+    /// image in `format`.
+    pub fn from_bytes(width: i32, height: i32, format: PixelFormat, data: &[u8]) -> Self {
+        let expected = unsafe { sys::GetPixelDataSize(width, height, format as c_int) } as usize;
+
+        assert_eq!(
+            data.len(),
+            expected,
+            "pixel data is {} bytes, expected {expected} for {width}x{height} {format:?}",
+            data.len()
+        );
+
+        unsafe {
+            // Allocated through raylib so that `UnloadImage` can free it. An
+            // empty image has no data, as raylib may not allocate 0 bytes.
+            let buffer = if data.is_empty() {
+                ptr::null_mut()
+            } else {
+                let buffer = sys::MemAlloc(data.len() as c_uint) as *mut u8;
+                assert!(!buffer.is_null(), "out of memory");
+                ptr::copy_nonoverlapping(data.as_ptr(), buffer, data.len());
+                buffer
+            };
+
+            Self::from_raw(sys::Image {
+                data: buffer.cast(),
+                width,
+                height,
+                mipmaps: 1,
+                format: format as c_int,
+            })
+        }
+    }
+
+    /// Creates an image in `P`'s format from a slice of pixels.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `pixels` does not hold exactly `width * height` pixels.
+    pub fn from_pixels<P: Pixel>(width: i32, height: i32, pixels: &[P]) -> Self {
+        let bytes = unsafe {
+            slice::from_raw_parts(pixels.as_ptr() as *const u8, std::mem::size_of_val(pixels))
+        };
+
+        Self::from_bytes(width, height, P::FORMAT, bytes)
+    }
+
+    /// Writes the image to a file, choosing the format from its extension.
+    pub fn export(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let c_path = crate::fs::to_cstring(path)?;
+        let (exported, mut messages) =
+            log::capture(|| unsafe { sys::ExportImage(self.raw, c_path.as_ptr()) });
+
+        if exported {
+            Ok(())
+        } else {
+            Err(Error::Export {
+                path: path.to_path_buf(),
+                message: messages.pop(),
+            })
+        }
+    }
+
+    /// Encodes the image to file data in memory. `file_type` is the file
+    /// extension, such as `".png"`.
+    pub fn export_to_memory(&self, file_type: &str) -> Result<Vec<u8>> {
+        let c_file_type = crate::fs::to_cstring(Path::new(file_type))?;
+
+        let (data, mut messages) = log::capture(|| unsafe {
+            let mut size = 0;
+            let data = sys::ExportImageToMemory(self.raw, c_file_type.as_ptr(), &mut size);
+
+            if data.is_null() {
+                return None;
+            }
+
+            let bytes = slice::from_raw_parts(data, size.max(0) as usize).to_vec();
+            sys::MemFree(data.cast());
+            Some(bytes)
+        });
+
+        data.ok_or_else(|| Error::Export {
+            path: Path::new("<memory>").to_path_buf(),
+            message: messages.pop(),
+        })
+    }
+
+    /// Encodes the image as a PNG in memory.
+    pub fn to_png(&self) -> Result<Vec<u8>> {
+        self.export_to_memory(".png")
+    }
+
+    /// Takes ownership of a raw image.
+    ///
+    /// # Safety
+    ///
+    /// `raw` must be a valid image whose data was allocated by raylib and is
+    /// not freed anywhere else.
+    pub unsafe fn from_raw(raw: sys::Image) -> Self {
+        Self { raw }
+    }
+
+    /// Releases ownership of the image without unloading it.
+    pub fn into_raw(self) -> sys::Image {
+        ManuallyDrop::new(self).raw
+    }
+
+    pub fn as_raw(&self) -> &sys::Image {
+        &self.raw
+    }
+
+    pub fn width(&self) -> i32 {
+        self.raw.width
+    }
+
+    pub fn height(&self) -> i32 {
+        self.raw.height
+    }
+
+    pub fn mipmaps(&self) -> i32 {
+        self.raw.mipmaps
+    }
+
+    pub fn format(&self) -> PixelFormat {
+        pixel_format(self.raw.format)
+    }
+
+    /// The size in bytes of the image's data, including every mipmap level.
+    pub fn data_size(&self) -> usize {
+        if self.raw.data.is_null() {
+            return 0;
+        }
+
+        let (mut width, mut height) = (self.raw.width, self.raw.height);
+        let mut size = 0;
+
+        for _ in 0..self.raw.mipmaps.max(1) {
+            size += unsafe { sys::GetPixelDataSize(width, height, self.raw.format) } as usize;
+            width = (width / 2).max(1);
+            height = (height / 2).max(1);
+        }
+
+        size
+    }
+
+    /// The image's raw data, including every mipmap level.
+    pub fn as_bytes(&self) -> &[u8] {
+        if self.raw.data.is_null() {
+            return &[];
+        }
+
+        unsafe { slice::from_raw_parts(self.raw.data as *const u8, self.data_size()) }
+    }
+
+    /// The image's raw data, including every mipmap level.
+    pub fn as_bytes_mut(&mut self) -> &mut [u8] {
+        if self.raw.data.is_null() {
+            return &mut [];
+        }
+
+        unsafe { slice::from_raw_parts_mut(self.raw.data as *mut u8, self.data_size()) }
+    }
+
+    /// The pixels of the base mipmap level, row by row, if the image is in
+    /// `P`'s format.
+    pub fn pixels<P: Pixel>(&self) -> Option<&[P]> {
+        if self.raw.format != P::FORMAT as c_int || self.raw.data.is_null() {
+            return None;
+        }
+
+        let len = self.pixel_count();
+        Some(unsafe { slice::from_raw_parts(self.raw.data as *const P, len) })
+    }
+
+    /// The pixels of the base mipmap level, row by row, if the image is in
+    /// `P`'s format.
+    pub fn pixels_mut<P: Pixel>(&mut self) -> Option<&mut [P]> {
+        if self.raw.format != P::FORMAT as c_int || self.raw.data.is_null() {
+            return None;
+        }
+
+        let len = self.pixel_count();
+        Some(unsafe { slice::from_raw_parts_mut(self.raw.data as *mut P, len) })
+    }
+
+    /// Reads a pixel in any uncompressed format, converted to a color.
+    ///
+    /// Returns `None` if the position is outside the image. Compressed
+    /// formats read as blank.
+    pub fn get_pixel(&self, x: i32, y: i32) -> Option<Color> {
+        if !self.contains(x, y) {
+            return None;
+        }
+
+        Some(unsafe { sys::GetImageColor(self.raw, x, y) })
+    }
+
+    /// Writes a pixel in any uncompressed format, converting from a color.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the position is outside the image.
+    pub fn set_pixel(&mut self, x: i32, y: i32, color: Color) {
+        assert!(
+            self.contains(x, y),
+            "pixel ({x}, {y}) is outside the {}x{} image",
+            self.raw.width,
+            self.raw.height
+        );
+
+        unsafe { sys::ImageDrawPixel(&mut self.raw, x, y, color) }
+    }
+
+    fn contains(&self, x: i32, y: i32) -> bool {
+        (0..self.raw.width).contains(&x) && (0..self.raw.height).contains(&y)
+    }
+
+    fn pixel_count(&self) -> usize {
+        self.raw.width.max(0) as usize * self.raw.height.max(0) as usize
+    }
+}
+
+impl Drop for OwnedImage {
+    fn drop(&mut self) {
+        if !self.raw.data.is_null() {
+            unsafe { sys::UnloadImage(self.raw) }
+        }
+    }
+}
+
+/// Converts a raw `format` field to a [`PixelFormat`].
+///
+/// # Panics
+///
+/// Panics if `format` is not a raylib pixel format.
+pub(crate) fn pixel_format(format: c_int) -> PixelFormat {
+    use PixelFormat::*;
+
+    const FORMATS: [PixelFormat; 24] = [
+        PIXELFORMAT_UNCOMPRESSED_GRAYSCALE,
+        PIXELFORMAT_UNCOMPRESSED_GRAY_ALPHA,
+        PIXELFORMAT_UNCOMPRESSED_R5G6B5,
+        PIXELFORMAT_UNCOMPRESSED_R8G8B8,
+        PIXELFORMAT_UNCOMPRESSED_R5G5B5A1,
+        PIXELFORMAT_UNCOMPRESSED_R4G4B4A4,
+        PIXELFORMAT_UNCOMPRESSED_R8G8B8A8,
+        PIXELFORMAT_UNCOMPRESSED_R32,
+        PIXELFORMAT_UNCOMPRESSED_R32G32B32,
+        PIXELFORMAT_UNCOMPRESSED_R32G32B32A32,
+        PIXELFORMAT_UNCOMPRESSED_R16,
+        PIXELFORMAT_UNCOMPRESSED_R16G16B16,
+        PIXELFORMAT_UNCOMPRESSED_R16G16B16A16,
+        PIXELFORMAT_COMPRESSED_DXT1_RGB,
+        PIXELFORMAT_COMPRESSED_DXT1_RGBA,
+        PIXELFORMAT_COMPRESSED_DXT3_RGBA,
+        PIXELFORMAT_COMPRESSED_DXT5_RGBA,
+        PIXELFORMAT_COMPRESSED_ETC1_RGB,
+        PIXELFORMAT_COMPRESSED_ETC2_RGB,
+        PIXELFORMAT_COMPRESSED_ETC2_EAC_RGBA,
+        PIXELFORMAT_COMPRESSED_PVRT_RGB,
+        PIXELFORMAT_COMPRESSED_PVRT_RGBA,
+        PIXELFORMAT_COMPRESSED_ASTC_4x4_RGBA,
+        PIXELFORMAT_COMPRESSED_ASTC_8x8_RGBA,
+    ];
+
+    FORMATS
+        .into_iter()
+        .find(|f| *f as c_int == format)
+        .unwrap_or_else(|| panic!("unknown pixel format {format}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BLUE, RED, WHITE};
+
+    const GRAYSCALE: PixelFormat = PixelFormat::PIXELFORMAT_UNCOMPRESSED_GRAYSCALE;
+    const RGBA: PixelFormat = PixelFormat::PIXELFORMAT_UNCOMPRESSED_R8G8B8A8;
+
+    #[test]
+    fn from_bytes_keeps_the_data() {
+        let data = [0, 64, 128, 255, 1, 2];
+        let image = OwnedImage::from_bytes(3, 2, GRAYSCALE, &data);
+
+        assert_eq!((image.width(), image.height(), image.mipmaps()), (3, 2, 1));
+        assert_eq!(image.format(), GRAYSCALE);
+        assert_eq!(image.as_bytes(), data);
+        assert_eq!(image.pixels::<u8>(), Some(&data[..]));
+    }
+
+    #[test]
+    #[should_panic(expected = "pixel data is 5 bytes, expected 6")]
+    fn from_bytes_rejects_short_data() {
+        OwnedImage::from_bytes(3, 2, GRAYSCALE, &[0; 5]);
+    }
+
+    #[test]
+    #[should_panic(expected = "pixel data is 28 bytes, expected 24")]
+    fn from_pixels_rejects_extra_pixels() {
+        OwnedImage::from_pixels(3, 2, &[RED; 7]);
+    }
+
+    #[test]
+    fn empty_images_have_no_data() {
+        let image = OwnedImage::from_bytes(0, 0, RGBA, &[]);
+
+        assert_eq!(image.data_size(), 0);
+        assert!(image.as_bytes().is_empty());
+        assert_eq!(image.pixels::<Color>(), None);
+        assert_eq!(image.get_pixel(0, 0), None);
+    }
+
+    #[test]
+    fn pixels_checks_the_format() {
+        let mut image = OwnedImage::from_pixels(2, 1, &[RED, BLUE]);
+
+        assert_eq!(image.pixels::<Color>(), Some(&[RED, BLUE][..]));
+        assert_eq!(image.pixels::<[u8; 4]>().map(<[_]>::len), Some(2));
+        assert_eq!(image.pixels::<u8>(), None);
+        assert_eq!(image.pixels::<f32>(), None);
+        assert!(image.pixels_mut::<[u8; 3]>().is_none());
+
+        image.pixels_mut::<Color>().unwrap()[1] = WHITE;
+        assert_eq!(image.get_pixel(1, 0), Some(WHITE));
+    }
+
+    #[test]
+    fn get_pixel_is_none_outside_the_image() {
+        let image = OwnedImage::new(4, 3, RED);
+
+        assert_eq!(image.get_pixel(0, 0), Some(RED));
+        assert_eq!(image.get_pixel(3, 2), Some(RED));
+
+        for (x, y) in [(-1, 0), (0, -1), (4, 0), (0, 3), (i32::MAX, i32::MIN)] {
+            assert_eq!(image.get_pixel(x, y), None);
+        }
+    }
+
+    #[test]
+    fn set_pixel_converts_the_color() {
+        let mut image = OwnedImage::from_bytes(2, 1, GRAYSCALE, &[0, 0]);
+        image.set_pixel(1, 0, WHITE);
+
+        assert_eq!(image.as_bytes(), [0, 255]);
+    }
+
+    #[test]
+    #[should_panic(expected = "pixel (4, 0) is outside the 4x3 image")]
+    fn set_pixel_panics_outside_the_image() {
+        OwnedImage::new(4, 3, RED).set_pixel(4, 0, BLUE);
+    }
+
+    #[test]
+    fn png_round_trips() {
+        let pixels = [RED, BLUE, WHITE, Color::new(1, 2, 3, 4), RED, BLUE];
+        let image = OwnedImage::from_pixels(3, 2, &pixels);
+
+        let png = image.export_to_memory(".png").unwrap();
+        assert!(png.starts_with(b"\x89PNG"));
+
+        let decoded = OwnedImage::load_from_memory(".png", &png).unwrap();
+        assert_eq!((decoded.width(), decoded.height()), (3, 2));
+        assert_eq!(decoded.pixels::<Color>(), Some(&pixels[..]));
+    }
+
+    #[test]
+    fn export_to_memory_rejects_unknown_formats() {
+        let image = OwnedImage::new(1, 1, RED);
+
+        assert!(matches!(
+            image.export_to_memory(".nope"),
+            Err(Error::Export { .. })
+        ));
+    }
+
+    #[test]
+    fn data_size_includes_mipmaps() {
+        let mut image = OwnedImage::new(4, 4, RED);
+        assert_eq!(image.data_size(), 64);
+
+        image.gen_mipmaps();
+
+        assert_eq!(image.mipmaps(), 3);
+        assert_eq!(image.data_size(), 64 + 16 + 4);
+        assert_eq!(image.as_bytes().len(), image.data_size());
+        assert_eq!(image.pixels::<Color>().map(<[_]>::len), Some(16));
+    }
+}
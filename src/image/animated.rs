@@ -0,0 +1,379 @@
+use super::OwnedImage;
+use crate::error::{self, Error, ResourceKind, Result};
+use crate::sys;
+use crate::sys::{Color, PixelFormat};
+use std::ffi::CString;
+use std::io;
+use std::os::raw::c_int;
+use std::path::Path;
+use std::slice;
+use std::time::Duration;
+
+/// The delay used for frames that don't specify one, matching what browsers
+/// do for GIFs with a zero delay.
+const DEFAULT_DELAY: Duration = Duration::from_millis(100);
+
+/// A multi-frame image, such as an animated GIF, unloaded when dropped.
+///
+/// raylib stores every frame in one buffer, one after another. Formats other
+/// than GIF load as a single frame.
+#[derive(Debug)]
+pub struct AnimatedImage {
+    raw: sys::Image,
+    frame_count: usize,
+    delays: Vec<Duration>,
+}
+
+impl AnimatedImage {
+    /// Loads an animation from a file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let data = crate::fs::load_file_data(ResourceKind::Image, path)?;
+
+        let file_type = path
+            .extension()
+            .map(|ext| format!(".{}", ext.to_string_lossy()))
+            .unwrap_or_default();
+
+        Self::decode(path, &file_type, &data)
+    }
+
+    /// Decodes an animation from file data in memory. `file_type` is the file
+    /// extension, such as `".gif"`.
+    pub fn load_from_memory(file_type: &str, data: &[u8]) -> Result<Self> {
+        Self::decode(Path::new("<memory>"), file_type, data)
+    }
+
+    fn decode(path: &Path, file_type: &str, data: &[u8]) -> Result<Self> {
+        let file_type = CString::new(file_type)
+            .map_err(|e| Error::Io(io::Error::new(io::ErrorKind::InvalidInput, e)))?;
+        let size = c_int::try_from(data.len())
+            .map_err(|e| Error::Io(io::Error::new(io::ErrorKind::InvalidInput, e)))?;
+
+        let mut frames = 0;
+        let image = error::load(
+            ResourceKind::Image,
+            path,
+            |_| unsafe {
+                OwnedImage::from_raw(sys::LoadImageAnimFromMemory(
+                    file_type.as_ptr(),
+                    data.as_ptr(),
+                    size,
+                    &mut frames,
+                ))
+            },
+            |image| unsafe { sys::IsImageValid(*image.as_raw()) },
+        )?;
+
+        let frame_count = frames.max(1) as usize;
+        let mut delays = gif_delays(data);
+        delays.resize(frame_count, DEFAULT_DELAY);
+
+        Ok(Self {
+            raw: image.into_raw(),
+            frame_count,
+            delays,
+        })
+    }
+
+    pub fn frame_count(&self) -> usize {
+        self.frame_count
+    }
+
+    /// The width of each frame.
+    pub fn width(&self) -> i32 {
+        self.raw.width
+    }
+
+    /// The height of each frame.
+    pub fn height(&self) -> i32 {
+        self.raw.height
+    }
+
+    pub fn format(&self) -> PixelFormat {
+        super::pixel_format(self.raw.format)
+    }
+
+    /// How long each frame is shown for.
+    pub fn delays(&self) -> &[Duration] {
+        &self.delays
+    }
+
+    /// How long one loop of the animation lasts.
+    pub fn duration(&self) -> Duration {
+        self.delays.iter().sum()
+    }
+
+    /// The index of the frame shown `time` into the animation, looping.
+    pub fn frame_at(&self, time: Duration) -> usize {
+        let total = self.duration();
+
+        if total.is_zero() {
+            return 0;
+        }
+
+        let mut remaining = Duration::from_nanos((time.as_nanos() % total.as_nanos()) as u64);
+
+        for (index, &delay) in self.delays.iter().enumerate() {
+            if remaining < delay {
+                return index;
+            }
+
+            remaining -= delay;
+        }
+
+        self.frame_count - 1
+    }
+
+    /// The raw bytes of frame `index`, or `None` if it is out of range.
+    pub fn frame_bytes(&self, index: usize) -> Option<&[u8]> {
+        if index >= self.frame_count || self.raw.data.is_null() {
+            return None;
+        }
+
+        let size = self.frame_size();
+
+        unsafe {
+            let data = (self.raw.data as *const u8).add(index * size);
+            Some(slice::from_raw_parts(data, size))
+        }
+    }
+
+    /// The pixels of frame `index`, or `None` if it is out of range or the
+    /// animation is not in R8G8B8A8 format (GIFs always are).
+    pub fn frame(&self, index: usize) -> Option<&[Color]> {
+        if self.raw.format != PixelFormat::PIXELFORMAT_UNCOMPRESSED_R8G8B8A8 as c_int {
+            return None;
+        }
+
+        let bytes = self.frame_bytes(index)?;
+        let len = bytes.len() / size_of::<Color>();
+        Some(unsafe { slice::from_raw_parts(bytes.as_ptr() as *const Color, len) })
+    }
+
+    /// Copies frame `index` into its own image.
+    pub fn frame_image(&self, index: usize) -> Option<OwnedImage> {
+        let bytes = self.frame_bytes(index)?;
+
+        Some(OwnedImage::from_bytes(
+            self.raw.width,
+            self.raw.height,
+            self.format(),
+            bytes,
+        ))
+    }
+
+    fn frame_size(&self) -> usize {
+        unsafe { sys::GetPixelDataSize(self.raw.width, self.raw.height, self.raw.format) as usize }
+    }
+}
+
+impl Drop for AnimatedImage {
+    fn drop(&mut self) {
+        if !self.raw.data.is_null() {
+            unsafe { sys::UnloadImage(self.raw) }
+        }
+    }
+}
+
+/// Reads the per-frame delays from a GIF's graphic control extensions.
+///
+/// raylib decodes the frames but discards their timing. Anything that isn't
+/// a well-formed GIF yields no delays.
+fn gif_delays(data: &[u8]) -> Vec<Duration> {
+    let mut delays = Vec::new();
+
+    if !data.starts_with(b"GIF87a") && !data.starts_with(b"GIF89a") {
+        return delays;
+    }
+
+    let Some(&flags) = data.get(10) else {
+        return delays;
+    };
+
+    let mut pos = 13 + color_table_size(flags);
+    let mut pending = None;
+
+    while let Some(&block) = data.get(pos) {
+        match block {
+            // Extension: a graphic control extension sets the next frame's
+            // delay in hundredths of a second.
+            0x21 => {
+                if data.get(pos + 1) == Some(&0xF9) && data.get(pos + 2) == Some(&4) {
+                    if let Some(delay) = data.get(pos + 4..pos + 6) {
+                        pending = Some(u16::from_le_bytes([delay[0], delay[1]]));
+                    }
+                }
+
+                pos = skip_sub_blocks(data, pos + 2);
+            }
+            // Image descriptor: one frame.
+            0x2C => {
+                let Some(&flags) = data.get(pos + 9) else {
+                    break;
+                };
+
+                let delay = match pending.take() {
+                    Some(0) | None => DEFAULT_DELAY,
+                    Some(centis) => Duration::from_millis(u64::from(centis) * 10),
+                };
+
+                delays.push(delay);
+                pos = skip_sub_blocks(data, pos + 10 + color_table_size(flags) + 1);
+            }
+            _ => break,
+        }
+    }
+
+    delays
+}
+
+/// The size of the color table described by a GIF header or image
+/// descriptor's packed flags.
+fn color_table_size(flags: u8) -> usize {
+    if flags & 0x80 != 0 {
+        3 << ((flags & 0x07) + 1)
+    } else {
+        0
+    }
+}
+
+/// Skips a chain of GIF data sub-blocks starting at `pos`, returning the
+/// position after the terminating empty block.
+fn skip_sub_blocks(data: &[u8], mut pos: usize) -> usize {
+    while let Some(&len) = data.get(pos) {
+        pos += 1 + len as usize;
+
+        if len == 0 {
+            break;
+        }
+    }
+
+    pos
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A GIF89a header for a 1x1 canvas with a two-color global color table.
+    fn header() -> Vec<u8> {
+        let mut data = b"GIF89a".to_vec();
+        data.extend([1, 0, 1, 0, 0x80, 0, 0]);
+        data.extend([0, 0, 0, 255, 255, 255]);
+        data
+    }
+
+    fn graphic_control(centis: u16) -> Vec<u8> {
+        let [lo, hi] = centis.to_le_bytes();
+        vec![0x21, 0xF9, 4, 0, lo, hi, 0, 0]
+    }
+
+    /// A 1x1 frame, optionally with a two-color local color table.
+    fn image(local_table: bool) -> Vec<u8> {
+        let flags = if local_table { 0x80 } else { 0 };
+        let mut data = vec![0x2C, 0, 0, 0, 0, 1, 0, 1, 0, flags];
+
+        if local_table {
+            data.extend([0, 0, 0, 255, 255, 255]);
+        }
+
+        data.extend([2, 2, 0x4C, 0x01, 0]);
+        data
+    }
+
+    fn gif(blocks: &[Vec<u8>]) -> Vec<u8> {
+        let mut data = header();
+        blocks.iter().for_each(|block| data.extend(block));
+        data.push(0x3B);
+        data
+    }
+
+    fn millis(values: &[u64]) -> Vec<Duration> {
+        values.iter().copied().map(Duration::from_millis).collect()
+    }
+
+    #[test]
+    fn frames_without_a_graphic_control_use_the_default_delay() {
+        assert_eq!(gif_delays(&gif(&[image(false)])), [DEFAULT_DELAY]);
+    }
+
+    #[test]
+    fn each_graphic_control_sets_the_next_frame() {
+        let data = gif(&[
+            graphic_control(5),
+            image(false),
+            graphic_control(20),
+            image(true),
+            image(false),
+            graphic_control(150),
+            image(false),
+        ]);
+
+        assert_eq!(gif_delays(&data), millis(&[50, 200, 100, 1500]));
+    }
+
+    #[test]
+    fn zero_delays_use_the_default() {
+        let data = gif(&[
+            graphic_control(0),
+            image(false),
+            graphic_control(1),
+            image(false),
+        ]);
+
+        assert_eq!(gif_delays(&data), millis(&[100, 10]));
+    }
+
+    #[test]
+    fn other_extensions_are_skipped() {
+        let mut application = vec![0x21, 0xFF, 11];
+        application.extend(b"NETSCAPE2.0");
+        application.extend([3, 1, 0, 0, 0]);
+
+        let mut comment = vec![0x21, 0xFE, 5];
+        comment.extend(b"hello");
+        comment.push(0);
+
+        let data = gif(&[
+            application,
+            comment.clone(),
+            graphic_control(7),
+            comment,
+            image(false),
+        ]);
+
+        assert_eq!(gif_delays(&data), millis(&[70]));
+    }
+
+    #[test]
+    fn truncated_streams_keep_the_complete_frames() {
+        let data = gif(&[
+            graphic_control(3),
+            image(false),
+            graphic_control(4),
+            image(false),
+        ]);
+        let second_frame = data.len() - 1 - image(false).len();
+
+        assert_eq!(gif_delays(&data[..second_frame + 5]), millis(&[30]));
+        assert_eq!(gif_delays(&data[..second_frame - 3]), millis(&[30]));
+        assert_eq!(gif_delays(&data[..12]), []);
+    }
+
+    #[test]
+    fn non_gifs_have_no_delays() {
+        assert_eq!(gif_delays(b""), []);
+        assert_eq!(gif_delays(b"GIF8"), []);
+        assert_eq!(gif_delays(b"\x89PNG\r\n\x1a\n"), []);
+        assert_eq!(gif_delays(b"GIF87a"), []);
+    }
+
+    #[test]
+    fn color_table_sizes() {
+        assert_eq!(color_table_size(0x00), 0);
+        assert_eq!(color_table_size(0x07), 0);
+        assert_eq!(color_table_size(0x80), 6);
+        assert_eq!(color_table_size(0x87), 768);
+    }
+}
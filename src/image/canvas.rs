@@ -0,0 +1,185 @@
+use super::OwnedImage;
+use crate::draw::{text_cstring, Canvas};
+use crate::font::{ImageFont, OwnedFont};
+use crate::sys;
+use crate::sys::{Color, Rectangle, Vector2};
+use std::os::raw::c_int;
+
+/// The gap between lines of text, matching raylib's default.
+const LINE_SPACING: f32 = 2.0;
+
+/// Draws onto an [`OwnedImage`] on the CPU, without needing a window.
+///
+/// Created by [`OwnedImage::canvas`]. raylib's image rasterizer works in
+/// whole pixels, so radii and line thicknesses are rounded.
+///
+/// [`Canvas::draw_text`] takes an [`OwnedFont`], which needs a window. Use
+/// [`Self::draw_text_cpu`] with an [`ImageFont`] to draw text without one.
+#[derive(Debug)]
+pub struct ImageCanvas<'a> {
+    image: &'a mut OwnedImage,
+}
+
+impl OwnedImage {
+    /// Borrows the image for drawing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the image is compressed.
+    pub fn canvas(&mut self) -> ImageCanvas<'_> {
+        self.check_uncompressed();
+        ImageCanvas { image: self }
+    }
+}
+
+impl ImageCanvas<'_> {
+    /// Draws the `source` region of an image into `dest`, scaling as needed.
+    pub fn draw_image(
+        &mut self,
+        image: &OwnedImage,
+        source: Rectangle,
+        dest: Rectangle,
+        tint: Color,
+    ) {
+        unsafe { sys::ImageDraw(self.raw(), image.raw, source, dest, tint) }
+    }
+
+    /// Draws text with its top-left corner at `position`, using a font
+    /// rasterized on the CPU, so no window is needed.
+    ///
+    /// Text is laid out like [`Canvas::draw_text`]: glyphs are scaled from
+    /// the font's size to `font_size`, `spacing` is added between them, and
+    /// `'\n'` starts a new line. Text stops at the first NUL character.
+    pub fn draw_text_cpu(
+        &mut self,
+        font: &ImageFont,
+        text: &str,
+        position: Vector2,
+        font_size: f32,
+        spacing: f32,
+        tint: Color,
+    ) {
+        let scale = font_size / font.size() as f32;
+        let mut offset = Vector2::ZERO;
+
+        for c in text.chars().take_while(|&c| c != '\0') {
+            if c == '\n' {
+                offset = Vector2::new(0.0, offset.y + font_size + LINE_SPACING);
+                continue;
+            }
+
+            let glyph = font.glyph(c);
+            let image = glyph.image;
+
+            if c != ' ' && c != '\t' {
+                let source = Rectangle {
+                    x: 0.0,
+                    y: 0.0,
+                    width: image.width as f32,
+                    height: image.height as f32,
+                };
+                let dest = Rectangle {
+                    x: position.x + offset.x + glyph.offsetX as f32 * scale,
+                    y: position.y + offset.y + glyph.offsetY as f32 * scale,
+                    width: image.width as f32 * scale,
+                    height: image.height as f32 * scale,
+                };
+
+                unsafe { sys::ImageDraw(self.raw(), image, source, dest, tint) }
+            }
+
+            let advance = if glyph.advanceX == 0 {
+                image.width
+            } else {
+                glyph.advanceX
+            };
+
+            offset.x += advance as f32 * scale + spacing;
+        }
+    }
+
+    pub fn image(&self) -> &OwnedImage {
+        self.image
+    }
+
+    fn raw(&mut self) -> *mut sys::Image {
+        &mut self.image.raw
+    }
+}
+
+impl Canvas for ImageCanvas<'_> {
+    fn clear(&mut self, color: Color) {
+        unsafe { sys::ImageClearBackground(self.raw(), color) }
+    }
+
+    fn draw_pixel(&mut self, position: Vector2, color: Color) {
+        unsafe { sys::ImageDrawPixelV(self.raw(), position, color) }
+    }
+
+    fn draw_line(&mut self, start: Vector2, end: Vector2, thickness: f32, color: Color) {
+        let thickness = thickness.round() as c_int;
+
+        unsafe {
+            if thickness <= 1 {
+                sys::ImageDrawLineV(self.raw(), start, end, color)
+            } else {
+                sys::ImageDrawLineEx(self.raw(), start, end, thickness, color)
+            }
+        }
+    }
+
+    fn draw_circle(&mut self, center: Vector2, radius: f32, color: Color) {
+        unsafe { sys::ImageDrawCircleV(self.raw(), center, radius.round() as c_int, color) }
+    }
+
+    fn draw_circle_lines(&mut self, center: Vector2, radius: f32, color: Color) {
+        unsafe { sys::ImageDrawCircleLinesV(self.raw(), center, radius.round() as c_int, color) }
+    }
+
+    fn draw_rectangle(&mut self, rect: Rectangle, color: Color) {
+        unsafe { sys::ImageDrawRectangleRec(self.raw(), rect, color) }
+    }
+
+    fn draw_rectangle_lines(&mut self, rect: Rectangle, thickness: f32, color: Color) {
+        let thickness = (thickness.round() as c_int).max(1);
+        unsafe { sys::ImageDrawRectangleLines(self.raw(), rect, thickness, color) }
+    }
+
+    fn draw_triangle(&mut self, v1: Vector2, v2: Vector2, v3: Vector2, color: Color) {
+        unsafe { sys::ImageDrawTriangle(self.raw(), v1, v2, v3, color) }
+    }
+
+    fn draw_triangle_lines(&mut self, v1: Vector2, v2: Vector2, v3: Vector2, color: Color) {
+        unsafe { sys::ImageDrawTriangleLines(self.raw(), v1, v2, v3, color) }
+    }
+
+    fn draw_triangle_fan(&mut self, points: &[Vector2], color: Color) {
+        // raylib only reads the points, despite the mutable pointer.
+        let points_ptr = points.as_ptr() as *mut Vector2;
+        unsafe { sys::ImageDrawTriangleFan(self.raw(), points_ptr, points.len() as c_int, color) }
+    }
+
+    fn draw_text(
+        &mut self,
+        font: &OwnedFont,
+        text: &str,
+        position: Vector2,
+        font_size: f32,
+        spacing: f32,
+        tint: Color,
+    ) {
+        let text = text_cstring(text);
+
+        unsafe {
+            sys::ImageDrawTextEx(
+                self.raw(),
+                *font.as_raw(),
+                text.as_ptr(),
+                position,
+                font_size,
+                spacing,
+                tint,
+            )
+        }
+    }
+}
@@ -0,0 +1,639 @@
+use super::OwnedImage;
+use crate::color::ColorSpace;
+use crate::draw::text_cstring;
+use crate::sys;
+use crate::sys::{Color, PixelFormat};
+use crate::BLANK;
+
+/// An image filled with a single color.
+pub fn color(width: i32, height: i32, color: Color) -> OwnedImage {
+    OwnedImage::new(width, height, color)
+}
+
+/// A linear gradient from `start` to `end`. A `direction` of 0 runs left to
+/// right, and other angles rotate it clockwise in degrees.
+pub fn gradient_linear(
+    width: i32,
+    height: i32,
+    direction: i32,
+    start: Color,
+    end: Color,
+) -> OwnedImage {
+    unsafe {
+        OwnedImage::from_raw(sys::GenImageGradientLinear(
+            width, height, direction, start, end,
+        ))
+    }
+}
+
+/// A radial gradient from `inner` at the center to `outer` at the edges.
+/// `density` from 0 to 1 sets how much of the radius is solid `inner`.
+pub fn gradient_radial(
+    width: i32,
+    height: i32,
+    density: f32,
+    inner: Color,
+    outer: Color,
+) -> OwnedImage {
+    unsafe {
+        OwnedImage::from_raw(sys::GenImageGradientRadial(
+            width, height, density, inner, outer,
+        ))
+    }
+}
+
+/// Like [`gradient_radial`], but falling off towards a square edge.
+pub fn gradient_square(
+    width: i32,
+    height: i32,
+    density: f32,
+    inner: Color,
+    outer: Color,
+) -> OwnedImage {
+    unsafe {
+        OwnedImage::from_raw(sys::GenImageGradientSquare(
+            width, height, density, inner, outer,
+        ))
+    }
+}
+
+/// A checkerboard with checks of `check_width` by `check_height` pixels.
+pub fn checked(
+    width: i32,
+    height: i32,
+    check_width: i32,
+    check_height: i32,
+    first: Color,
+    second: Color,
+) -> OwnedImage {
+    unsafe {
+        OwnedImage::from_raw(sys::GenImageChecked(
+            width,
+            height,
+            check_width,
+            check_height,
+            first,
+            second,
+        ))
+    }
+}
+
+/// Black and white noise, where `factor` is the fraction of white pixels.
+///
+/// Uses raylib's random number generator; see [`crate::random::Rng`].
+pub fn white_noise(width: i32, height: i32, factor: f32) -> OwnedImage {
+    unsafe { OwnedImage::from_raw(sys::GenImageWhiteNoise(width, height, factor)) }
+}
+
+/// raylib's grayscale fractal Perlin noise, sampled from `(offset_x,
+/// offset_y)` at the given `scale`.
+pub fn perlin_noise(
+    width: i32,
+    height: i32,
+    offset_x: i32,
+    offset_y: i32,
+    scale: f32,
+) -> OwnedImage {
+    unsafe {
+        OwnedImage::from_raw(sys::GenImagePerlinNoise(
+            width, height, offset_x, offset_y, scale,
+        ))
+    }
+}
+
+/// Grayscale Worley (cellular) noise with one feature point per
+/// `tile_size` square.
+///
+/// Uses raylib's random number generator; see [`crate::random::Rng`].
+pub fn cellular(width: i32, height: i32, tile_size: i32) -> OwnedImage {
+    unsafe { OwnedImage::from_raw(sys::GenImageCellular(width, height, tile_size)) }
+}
+
+/// A grayscale image whose pixel bytes are the bytes of `text`.
+///
+/// Text stops at the first NUL character.
+pub fn text(width: i32, height: i32, text: &str) -> OwnedImage {
+    let text = text_cstring(text);
+    unsafe { OwnedImage::from_raw(sys::GenImageText(width, height, text.as_ptr())) }
+}
+
+/// A 2D noise function returning values roughly between -1 and 1.
+pub trait Noise {
+    fn sample(&self, x: f32, y: f32) -> f32;
+
+    /// Layers `octaves` copies of this noise at doubling frequencies and
+    /// halving amplitudes.
+    fn fractal(self, octaves: u32) -> Fractal<Self>
+    where
+        Self: Sized,
+    {
+        Fractal::new(self, octaves)
+    }
+
+    /// Offsets every sample position by `warp`, scaled by `strength`.
+    fn warp<W: Noise>(self, warp: W, strength: f32) -> DomainWarp<Self, W>
+    where
+        Self: Sized,
+    {
+        DomainWarp::new(self, warp, strength)
+    }
+}
+
+impl<N: Noise + ?Sized> Noise for &N {
+    fn sample(&self, x: f32, y: f32) -> f32 {
+        (**self).sample(x, y)
+    }
+}
+
+/// Seeded 2D gradient noise, implemented in Rust so results are the same on
+/// every platform and independent of raylib's random state.
+#[derive(Debug, Clone)]
+pub struct Perlin {
+    permutation: [u8; 512],
+}
+
+impl Perlin {
+    pub fn new(seed: u64) -> Self {
+        let mut table: [u8; 256] = std::array::from_fn(|i| i as u8);
+        let mut state = seed;
+
+        // Fisher-Yates with splitmix64, so each seed gives its own table.
+        for i in (1..table.len()).rev() {
+            state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+            let mut z = state;
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+            z ^= z >> 31;
+
+            table.swap(i, (z % (i as u64 + 1)) as usize);
+        }
+
+        Self {
+            permutation: std::array::from_fn(|i| table[i % 256]),
+        }
+    }
+
+    fn hash(&self, x: usize, y: usize) -> u8 {
+        self.permutation[self.permutation[x] as usize + y]
+    }
+}
+
+impl Noise for Perlin {
+    fn sample(&self, x: f32, y: f32) -> f32 {
+        let (x0, y0) = (x.floor(), y.floor());
+        let (fx, fy) = (x - x0, y - y0);
+        let xi = (x0 as i32 & 255) as usize;
+        let yi = (y0 as i32 & 255) as usize;
+
+        let corner = |dx: usize, dy: usize| {
+            gradient(self.hash(xi + dx, yi + dy), fx - dx as f32, fy - dy as f32)
+        };
+
+        let (u, v) = (fade(fx), fade(fy));
+        let bottom = lerp(corner(0, 0), corner(1, 0), u);
+        let top = lerp(corner(0, 1), corner(1, 1), u);
+
+        lerp(bottom, top, v)
+    }
+}
+
+/// Fractal Brownian motion: the sum of several octaves of a noise source.
+#[derive(Debug, Clone)]
+pub struct Fractal<N> {
+    noise: N,
+    octaves: u32,
+    lacunarity: f32,
+    gain: f32,
+}
+
+impl<N: Noise> Fractal<N> {
+    pub fn new(noise: N, octaves: u32) -> Self {
+        Self {
+            noise,
+            octaves: octaves.max(1),
+            lacunarity: 2.0,
+            gain: 0.5,
+        }
+    }
+
+    /// Sets the frequency multiplier between octaves, 2 by default.
+    pub fn with_lacunarity(mut self, lacunarity: f32) -> Self {
+        self.lacunarity = lacunarity;
+        self
+    }
+
+    /// Sets the amplitude multiplier between octaves, 0.5 by default.
+    pub fn with_gain(mut self, gain: f32) -> Self {
+        self.gain = gain;
+        self
+    }
+}
+
+impl<N: Noise> Noise for Fractal<N> {
+    fn sample(&self, x: f32, y: f32) -> f32 {
+        let (mut sum, mut total) = (0.0, 0.0);
+        let (mut frequency, mut amplitude) = (1.0, 1.0);
+
+        for _ in 0..self.octaves {
+            sum += self.noise.sample(x * frequency, y * frequency) * amplitude;
+            total += amplitude;
+            frequency *= self.lacunarity;
+            amplitude *= self.gain;
+        }
+
+        // Dividing by the total amplitude keeps the result in range however
+        // many octaves there are.
+        if total == 0.0 {
+            0.0
+        } else {
+            sum / total
+        }
+    }
+}
+
+/// Distorts a noise source by offsetting its sample positions with another.
+#[derive(Debug, Clone)]
+pub struct DomainWarp<N, W> {
+    noise: N,
+    warp: W,
+    strength: f32,
+}
+
+impl<N: Noise, W: Noise> DomainWarp<N, W> {
+    pub fn new(noise: N, warp: W, strength: f32) -> Self {
+        Self {
+            noise,
+            warp,
+            strength,
+        }
+    }
+}
+
+impl<N: Noise, W: Noise> Noise for DomainWarp<N, W> {
+    fn sample(&self, x: f32, y: f32) -> f32 {
+        // Sampling the warp at a distant offset decorrelates the two axes.
+        let dx = self.warp.sample(x, y);
+        let dy = self.warp.sample(x + 31.7, y + 47.3);
+
+        self.noise
+            .sample(x + dx * self.strength, y + dy * self.strength)
+    }
+}
+
+/// A grid of values, one per pixel, such as a heightmap.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Field {
+    width: i32,
+    height: i32,
+    values: Vec<f32>,
+}
+
+impl Field {
+    /// Builds a field by calling `f` for every `(x, y)` position.
+    pub fn from_fn(width: i32, height: i32, mut f: impl FnMut(i32, i32) -> f32) -> Self {
+        let (width, height) = (width.max(0), height.max(0));
+        let values = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| f(x, y))
+            .collect();
+
+        Self {
+            width,
+            height,
+            values,
+        }
+    }
+
+    /// Samples `noise` at every pixel, where `scale` is the size in pixels of
+    /// one unit of noise space.
+    pub fn sample(width: i32, height: i32, scale: f32, noise: &impl Noise) -> Self {
+        Self::from_fn(width, height, |x, y| {
+            noise.sample(x as f32 / scale, y as f32 / scale)
+        })
+    }
+
+    /// Reads an image's luminance, from 0 to 1.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the image is compressed.
+    pub fn from_image(image: &OwnedImage) -> Self {
+        let gray = image.converted(PixelFormat::PIXELFORMAT_UNCOMPRESSED_GRAYSCALE);
+        let pixels: &[u8] = gray.pixels().unwrap_or_default();
+
+        Self {
+            width: gray.width(),
+            height: gray.height(),
+            values: pixels.iter().map(|&p| f32::from(p) / 255.0).collect(),
+        }
+    }
+
+    pub fn width(&self) -> i32 {
+        self.width
+    }
+
+    pub fn height(&self) -> i32 {
+        self.height
+    }
+
+    /// The values row by row.
+    pub fn values(&self) -> &[f32] {
+        &self.values
+    }
+
+    pub fn values_mut(&mut self) -> &mut [f32] {
+        &mut self.values
+    }
+
+    /// Returns `None` if the position is outside the field.
+    pub fn get(&self, x: i32, y: i32) -> Option<f32> {
+        if (0..self.width).contains(&x) && (0..self.height).contains(&y) {
+            Some(self.values[(y * self.width + x) as usize])
+        } else {
+            None
+        }
+    }
+
+    /// Applies `f` to every value.
+    pub fn map(mut self, mut f: impl FnMut(f32) -> f32) -> Self {
+        self.values.iter_mut().for_each(|v| *v = f(*v));
+        self
+    }
+
+    /// Combines two fields of the same size value by value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the fields differ in size.
+    pub fn zip(mut self, other: &Field, mut f: impl FnMut(f32, f32) -> f32) -> Self {
+        assert!(
+            self.width == other.width && self.height == other.height,
+            "fields are {}x{} and {}x{}",
+            self.width,
+            self.height,
+            other.width,
+            other.height
+        );
+
+        for (a, &b) in self.values.iter_mut().zip(&other.values) {
+            *a = f(*a, b);
+        }
+
+        self
+    }
+
+    /// Rescales the values so the smallest is 0 and the largest is 1.
+    pub fn normalized(self) -> Self {
+        let (min, max) = self
+            .values
+            .iter()
+            .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), &v| {
+                (min.min(v), max.max(v))
+            });
+
+        let range = max - min;
+
+        if range > 0.0 {
+            self.map(|v| (v - min) / range)
+        } else {
+            self.map(|_| 0.0)
+        }
+    }
+
+    /// Converts the field to a grayscale image, mapping 0 to black and 1 to
+    /// white. Values outside that range are clamped.
+    pub fn to_image(&self) -> OwnedImage {
+        let pixels: Vec<u8> = self.values.iter().map(|&v| unit_to_byte(v)).collect();
+        OwnedImage::from_pixels(self.width, self.height, &pixels)
+    }
+
+    /// Converts the field to an RGBA image, coloring each value with
+    /// `gradient`.
+    pub fn to_image_with(&self, gradient: &GradientMap) -> OwnedImage {
+        let pixels: Vec<Color> = self.values.iter().map(|&v| gradient.sample(v)).collect();
+        OwnedImage::from_pixels(self.width, self.height, &pixels)
+    }
+}
+
+/// A color ramp that maps values from 0 to 1 onto colors.
+#[derive(Debug, Clone, Default)]
+pub struct GradientMap {
+    stops: Vec<(f32, Color)>,
+    space: ColorSpace,
+}
+
+impl GradientMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a color stop at `position`.
+    pub fn stop(mut self, position: f32, color: Color) -> Self {
+        let index = self.stops.partition_point(|(p, _)| *p <= position);
+        self.stops.insert(index, (position, color));
+        self
+    }
+
+    /// Sets the color space stops are interpolated in. Defaults to sRGB;
+    /// [`ColorSpace::Oklch`] keeps ramps between saturated colors vivid.
+    pub fn with_space(mut self, space: ColorSpace) -> Self {
+        self.space = space;
+        self
+    }
+
+    /// The color at `position`, interpolated between the surrounding stops.
+    ///
+    /// Positions before the first stop or after the last take its color. An
+    /// empty map is transparent black.
+    pub fn sample(&self, position: f32) -> Color {
+        let index = self.stops.partition_point(|(p, _)| *p <= position);
+
+        match (self.stops.get(index.wrapping_sub(1)), self.stops.get(index)) {
+            (Some(&(p0, c0)), Some(&(p1, c1))) => {
+                c0.mix(c1, (position - p0) / (p1 - p0), self.space)
+            }
+            (Some(&(_, color)), None) | (None, Some(&(_, color))) => color,
+            (None, None) => BLANK,
+        }
+    }
+
+    /// Recolors an image by its luminance.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the image is compressed.
+    pub fn apply(&self, image: &OwnedImage) -> OwnedImage {
+        Field::from_image(image).to_image_with(self)
+    }
+}
+
+fn fade(t: f32) -> f32 {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+fn lerp(a: f32, b: f32, t: f32) -> f32 {
+    a + (b - a) * t
+}
+
+fn gradient(hash: u8, x: f32, y: f32) -> f32 {
+    match hash & 7 {
+        0 => x + y,
+        1 => -x + y,
+        2 => x - y,
+        3 => -x - y,
+        4 => x,
+        5 => -x,
+        6 => y,
+        _ => -y,
+    }
+}
+
+fn unit_to_byte(v: f32) -> u8 {
+    (v.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gradient_map_rounds_between_stops() {
+        let map = GradientMap::new()
+            .stop(0.0, Color::new(0, 0, 0, 0))
+            .stop(1.0, Color::new(255, 255, 255, 255));
+
+        // 127.5 rounds up, where `ColorLerp` would truncate to 127.
+        assert_eq!(map.sample(0.5), Color::new(128, 128, 128, 128));
+        assert_eq!(map.sample(0.2), Color::new(51, 51, 51, 51));
+        assert_eq!(map.sample(-1.0), Color::new(0, 0, 0, 0));
+        assert_eq!(map.sample(2.0), Color::new(255, 255, 255, 255));
+        assert_eq!(GradientMap::new().sample(0.5), BLANK);
+    }
+
+    #[test]
+    fn gradient_map_interpolates_between_surrounding_stops() {
+        let map = GradientMap::new()
+            .stop(1.0, Color::rgb(0, 0, 200))
+            .stop(0.0, Color::rgb(200, 0, 0))
+            .stop(0.5, Color::rgb(0, 200, 0));
+
+        assert_eq!(map.sample(0.25), Color::rgb(100, 100, 0));
+        assert_eq!(map.sample(0.75), Color::rgb(0, 100, 100));
+        assert_eq!(map.sample(0.5), Color::rgb(0, 200, 0));
+    }
+
+    /// Sample positions off the integer lattice, including negative ones.
+    fn positions() -> impl Iterator<Item = (f32, f32)> {
+        (0..400).map(|i| {
+            let i = i as f32;
+            ((i * 0.37).sin() * 40.0, (i * 0.113).cos() * 25.0 + i * 0.05)
+        })
+    }
+
+    #[test]
+    fn perlin_is_deterministic_per_seed() {
+        let (a, b, other) = (Perlin::new(7), Perlin::new(7), Perlin::new(8));
+
+        assert!(positions().all(|(x, y)| a.sample(x, y) == b.sample(x, y)));
+        assert!(positions().any(|(x, y)| a.sample(x, y) != other.sample(x, y)));
+        assert_ne!(a.permutation, other.permutation);
+    }
+
+    #[test]
+    fn perlin_matches_reference_values() {
+        // Pinned so the output can't drift between platforms or releases.
+        let noise = Perlin::new(42);
+
+        for (x, y, expected) in [
+            (2.3, 4.6, 0.418_766_2),
+            (1.25, -3.75, 0.154_520_99),
+            (-17.1, 200.9, 0.015_283_287),
+            (300.3, 0.7, -0.210_138_08),
+        ] {
+            let actual = noise.sample(x, y);
+            assert!((actual - expected).abs() < 1e-6, "{actual} != {expected}");
+        }
+    }
+
+    #[test]
+    fn perlin_is_zero_on_lattice_points() {
+        let noise = Perlin::new(3);
+
+        for x in -5..5 {
+            for y in -5..5 {
+                assert_eq!(noise.sample(x as f32, y as f32), 0.0);
+            }
+        }
+    }
+
+    #[test]
+    fn noise_stays_in_range() {
+        let perlin = Perlin::new(11);
+        let fractal = Perlin::new(12).fractal(6);
+        let warped = Perlin::new(13).warp(Perlin::new(14).fractal(3), 4.0);
+
+        for (x, y) in positions() {
+            for value in [
+                perlin.sample(x, y),
+                fractal.sample(x, y),
+                warped.sample(x, y),
+            ] {
+                assert!((-1.0..=1.0).contains(&value), "{value} at ({x}, {y})");
+            }
+        }
+    }
+
+    #[test]
+    fn fractal_with_one_octave_is_the_source() {
+        let noise = Perlin::new(5);
+        let fractal = Fractal::new(&noise, 0).with_gain(0.25);
+
+        assert!(positions().all(|(x, y)| fractal.sample(x, y) == noise.sample(x, y)));
+        assert_eq!(
+            Fractal::new(&noise, 3).with_gain(0.0).sample(0.5, 0.25),
+            noise.sample(0.5, 0.25)
+        );
+    }
+
+    #[test]
+    fn zero_strength_warp_is_the_source() {
+        let noise = Perlin::new(9);
+        let warped = (&noise).warp(Perlin::new(10), 0.0);
+
+        assert!(positions().all(|(x, y)| warped.sample(x, y) == noise.sample(x, y)));
+    }
+
+    #[test]
+    fn normalized_spans_zero_to_one() {
+        let field = Field::sample(32, 16, 8.0, &Perlin::new(1).fractal(4)).normalized();
+        let (min, max) = field
+            .values()
+            .iter()
+            .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), &v| {
+                (min.min(v), max.max(v))
+            });
+
+        assert_eq!((field.width(), field.height()), (32, 16));
+        assert_eq!((min, max), (0.0, 1.0));
+    }
+
+    #[test]
+    fn normalized_maps_a_constant_field_to_zero() {
+        let field = Field::from_fn(4, 3, |_, _| 0.75).normalized();
+
+        assert!(field.values().iter().all(|&v| v == 0.0));
+        assert!(Field::from_fn(0, 0, |_, _| 1.0)
+            .normalized()
+            .values()
+            .is_empty());
+    }
+
+    #[test]
+    fn field_get_is_row_major() {
+        let field = Field::from_fn(3, 2, |x, y| (y * 10 + x) as f32);
+
+        assert_eq!(field.get(2, 1), Some(12.0));
+        assert_eq!(field.get(3, 0), None);
+        assert_eq!(field.get(0, -1), None);
+    }
+}
@@ -0,0 +1,162 @@
+use super::OwnedImage;
+use crate::error::{Error, Result};
+use crate::sys::PixelFormat;
+use ::image::{DynamicImage, ImageBuffer, Rgba, RgbaImage};
+use std::mem::size_of_val;
+use std::path::Path;
+use std::slice;
+
+impl OwnedImage {
+    /// Borrows an R8G8B8A8 image as an `image` crate buffer, without copying.
+    pub fn as_rgba_view(&self) -> Option<ImageBuffer<Rgba<u8>, &[u8]>> {
+        let pixels: &[[u8; 4]] = self.pixels()?;
+        ImageBuffer::from_raw(
+            self.width() as u32,
+            self.height() as u32,
+            pixels.as_flattened(),
+        )
+    }
+
+    /// Mutably borrows an R8G8B8A8 image as an `image` crate buffer, without
+    /// copying.
+    pub fn as_rgba_view_mut(&mut self) -> Option<ImageBuffer<Rgba<u8>, &mut [u8]>> {
+        let (width, height) = (self.width() as u32, self.height() as u32);
+        let pixels: &mut [[u8; 4]] = self.pixels_mut()?;
+        ImageBuffer::from_raw(width, height, pixels.as_flattened_mut())
+    }
+}
+
+/// A channel type shared by raylib's and `image`'s pixel formats.
+///
+/// # Safety
+///
+/// Every bit pattern must be a valid value, and there must be no padding.
+unsafe trait Sample: Copy + Default {
+    fn as_bytes(samples: &[Self]) -> &[u8] {
+        unsafe { slice::from_raw_parts(samples.as_ptr().cast(), size_of_val(samples)) }
+    }
+
+    fn as_bytes_mut(samples: &mut [Self]) -> &mut [u8] {
+        unsafe { slice::from_raw_parts_mut(samples.as_mut_ptr().cast(), size_of_val(samples)) }
+    }
+}
+
+unsafe impl Sample for u8 {}
+unsafe impl Sample for f32 {}
+
+/// Copies an `image` buffer into a raylib image in `format`, which must
+/// have the same layout.
+fn from_buffer<P>(buffer: &ImageBuffer<P, Vec<P::Subpixel>>, format: PixelFormat) -> OwnedImage
+where
+    P: ::image::Pixel,
+    P::Subpixel: Sample,
+{
+    let (width, height) = (buffer.width() as i32, buffer.height() as i32);
+    OwnedImage::from_bytes(width, height, format, Sample::as_bytes(buffer.as_raw()))
+}
+
+/// Copies a raylib image's base level into an `image` buffer, if it is in
+/// `format`, which must have the same layout.
+fn to_buffer<P>(image: &OwnedImage, format: PixelFormat) -> Option<ImageBuffer<P, Vec<P::Subpixel>>>
+where
+    P: ::image::Pixel,
+    P::Subpixel: Sample,
+{
+    if image.format() != format {
+        return None;
+    }
+
+    let len = image.pixel_count() * usize::from(P::CHANNEL_COUNT);
+    let mut samples = vec![P::Subpixel::default(); len];
+    let bytes = Sample::as_bytes_mut(&mut samples);
+    bytes.copy_from_slice(image.as_bytes().get(..bytes.len())?);
+
+    ImageBuffer::from_raw(image.width() as u32, image.height() as u32, samples)
+}
+
+impl From<&RgbaImage> for OwnedImage {
+    fn from(value: &RgbaImage) -> Self {
+        from_buffer(value, PixelFormat::PIXELFORMAT_UNCOMPRESSED_R8G8B8A8)
+    }
+}
+
+impl From<RgbaImage> for OwnedImage {
+    fn from(value: RgbaImage) -> Self {
+        Self::from(&value)
+    }
+}
+
+/// Copies the image, mapping each `image` color type to the matching
+/// raylib pixel format. 16-bit images have no raylib equivalent and are
+/// converted to 32-bit float.
+impl From<&DynamicImage> for OwnedImage {
+    fn from(value: &DynamicImage) -> Self {
+        use PixelFormat::*;
+
+        match value {
+            DynamicImage::ImageLuma8(b) => from_buffer(b, PIXELFORMAT_UNCOMPRESSED_GRAYSCALE),
+            DynamicImage::ImageLumaA8(b) => from_buffer(b, PIXELFORMAT_UNCOMPRESSED_GRAY_ALPHA),
+            DynamicImage::ImageRgb8(b) => from_buffer(b, PIXELFORMAT_UNCOMPRESSED_R8G8B8),
+            DynamicImage::ImageRgba8(b) => from_buffer(b, PIXELFORMAT_UNCOMPRESSED_R8G8B8A8),
+            DynamicImage::ImageRgb32F(b) => from_buffer(b, PIXELFORMAT_UNCOMPRESSED_R32G32B32),
+            DynamicImage::ImageRgba32F(b) => from_buffer(b, PIXELFORMAT_UNCOMPRESSED_R32G32B32A32),
+            other => from_buffer(&other.to_rgba32f(), PIXELFORMAT_UNCOMPRESSED_R32G32B32A32),
+        }
+    }
+}
+
+impl From<DynamicImage> for OwnedImage {
+    fn from(value: DynamicImage) -> Self {
+        Self::from(&value)
+    }
+}
+
+/// Copies an R8G8B8A8 image's base level. Other formats fail with
+/// [`Error::UnsupportedFormat`]; convert them first with
+/// [`OwnedImage::converted`].
+impl TryFrom<&OwnedImage> for RgbaImage {
+    type Error = Error;
+
+    fn try_from(value: &OwnedImage) -> Result<Self> {
+        to_buffer(value, PixelFormat::PIXELFORMAT_UNCOMPRESSED_R8G8B8A8)
+            .ok_or(Error::UnsupportedFormat(value.format()))
+    }
+}
+
+/// Copies the image's base level into the matching `image` color type.
+///
+/// Fails with [`Error::UnsupportedFormat`] for compressed, packed 16-bit,
+/// half-float, and single-channel float formats, which `image` has no
+/// equivalent for.
+impl TryFrom<&OwnedImage> for DynamicImage {
+    type Error = Error;
+
+    fn try_from(value: &OwnedImage) -> Result<Self> {
+        use PixelFormat::*;
+
+        let format = value.format();
+        let image = match format {
+            PIXELFORMAT_UNCOMPRESSED_GRAYSCALE => to_buffer(value, format).map(Self::ImageLuma8),
+            PIXELFORMAT_UNCOMPRESSED_GRAY_ALPHA => to_buffer(value, format).map(Self::ImageLumaA8),
+            PIXELFORMAT_UNCOMPRESSED_R8G8B8 => to_buffer(value, format).map(Self::ImageRgb8),
+            PIXELFORMAT_UNCOMPRESSED_R8G8B8A8 => to_buffer(value, format).map(Self::ImageRgba8),
+            PIXELFORMAT_UNCOMPRESSED_R32G32B32 => to_buffer(value, format).map(Self::ImageRgb32F),
+            PIXELFORMAT_UNCOMPRESSED_R32G32B32A32 => {
+                to_buffer(value, format).map(Self::ImageRgba32F)
+            }
+            _ => None,
+        };
+
+        image.ok_or(Error::UnsupportedFormat(format))
+    }
+}
+
+/// Decodes in-memory file data with the `image` crate.
+pub(super) fn decode(data: &[u8]) -> Option<OwnedImage> {
+    ::image::load_from_memory(data).ok().map(OwnedImage::from)
+}
+
+/// Decodes a file with the `image` crate.
+pub(super) fn decode_file(path: &Path) -> Option<OwnedImage> {
+    ::image::open(path).ok().map(OwnedImage::from)
+}
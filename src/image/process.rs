@@ -0,0 +1,537 @@
+use super::OwnedImage;
+use crate::color::Palette;
+use crate::sys;
+use crate::sys::{Color, PixelFormat, Rectangle};
+use std::collections::HashMap;
+use std::os::raw::c_int;
+
+/// Defines each operation twice: as an in-place method that returns `&mut
+/// Self` for chaining, and as a method that returns a processed copy.
+macro_rules! operations {
+    ($(
+        $(#[$meta:meta])*
+        fn $name:ident / $copy:ident (&mut $self:ident $(, $arg:ident: $ty:ty)* $(,)?) $body:block
+    )*) => {
+        impl OwnedImage {
+            $(
+                $(#[$meta])*
+                pub fn $name(&mut $self $(, $arg: $ty)*) -> &mut Self {
+                    $body
+                    $self
+                }
+
+                #[doc = concat!("Returns a copy processed by [`Self::", stringify!($name), "`].")]
+                pub fn $copy(&self $(, $arg: $ty)*) -> Self {
+                    let mut copy = self.clone();
+                    copy.$name($($arg),*);
+                    copy
+                }
+            )*
+        }
+    };
+}
+
+operations! {
+    /// Converts the image to another pixel format.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the image or `format` is compressed, as raylib can only
+    /// convert between uncompressed formats.
+    fn convert / converted(&mut self, format: PixelFormat) {
+        self.check_uncompressed();
+        assert!(
+            (format as c_int) < PixelFormat::PIXELFORMAT_COMPRESSED_DXT1_RGB as c_int,
+            "cannot convert to compressed format {format:?}"
+        );
+        unsafe { sys::ImageFormat(&mut self.raw, format as c_int) }
+    }
+
+    /// Resizes the image with bicubic filtering.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either dimension is not positive, or if the image is
+    /// compressed.
+    fn resize / resized(&mut self, width: i32, height: i32) {
+        self.check_uncompressed();
+        check_size(width, height);
+        unsafe { sys::ImageResize(&mut self.raw, width, height) }
+    }
+
+    /// Resizes the image with nearest-neighbour sampling, which keeps pixel
+    /// art sharp.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either dimension is not positive, or if the image is
+    /// compressed.
+    fn resize_nearest / resized_nearest(&mut self, width: i32, height: i32) {
+        self.check_uncompressed();
+        check_size(width, height);
+        unsafe { sys::ImageResizeNN(&mut self.raw, width, height) }
+    }
+
+    /// Resizes the canvas without scaling, placing the current contents at
+    /// `(offset_x, offset_y)` and filling new space with `fill`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either dimension is not positive, or if the image is
+    /// compressed.
+    fn resize_canvas / resized_canvas(
+        &mut self,
+        width: i32,
+        height: i32,
+        offset_x: i32,
+        offset_y: i32,
+        fill: Color,
+    ) {
+        self.check_uncompressed();
+        check_size(width, height);
+        unsafe { sys::ImageResizeCanvas(&mut self.raw, width, height, offset_x, offset_y, fill) }
+    }
+
+    /// Crops the image to `rect`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rect` is empty or not inside the image, or if the image is
+    /// compressed.
+    fn crop / cropped(&mut self, rect: Rectangle) {
+        self.check_uncompressed();
+        assert!(
+            rect.x >= 0.0
+                && rect.y >= 0.0
+                && rect.width >= 1.0
+                && rect.height >= 1.0
+                && rect.x + rect.width <= self.raw.width as f32
+                && rect.y + rect.height <= self.raw.height as f32,
+            "crop rectangle {rect:?} is outside the {}x{} image",
+            self.raw.width,
+            self.raw.height
+        );
+        unsafe { sys::ImageCrop(&mut self.raw, rect) }
+    }
+
+    /// Crops away the border whose alpha is at or below `threshold`.
+    fn alpha_crop / alpha_cropped(&mut self, threshold: f32) {
+        self.check_uncompressed();
+        unsafe { sys::ImageAlphaCrop(&mut self.raw, threshold) }
+    }
+
+    /// Replaces every pixel whose alpha is at or below `threshold` with
+    /// `color`.
+    fn alpha_clear / alpha_cleared(&mut self, color: Color, threshold: f32) {
+        self.check_uncompressed();
+        unsafe { sys::ImageAlphaClear(&mut self.raw, color, threshold) }
+    }
+
+    /// Uses the brightness of `mask` as the image's alpha channel.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `mask` is not the same size as the image, or if either
+    /// image is compressed.
+    fn alpha_mask / alpha_masked(&mut self, mask: &OwnedImage) {
+        self.check_uncompressed();
+        mask.check_uncompressed();
+        assert!(
+            mask.raw.width == self.raw.width && mask.raw.height == self.raw.height,
+            "alpha mask is {}x{}, expected {}x{}",
+            mask.raw.width,
+            mask.raw.height,
+            self.raw.width,
+            self.raw.height
+        );
+        unsafe { sys::ImageAlphaMask(&mut self.raw, mask.raw) }
+    }
+
+    /// Multiplies the color channels by the alpha channel.
+    fn alpha_premultiply / alpha_premultiplied(&mut self) {
+        self.check_uncompressed();
+        unsafe { sys::ImageAlphaPremultiply(&mut self.raw) }
+    }
+
+    /// Rotates the image by a whole number of degrees, growing it to fit.
+    fn rotate / rotated(&mut self, degrees: i32) {
+        self.check_uncompressed();
+        unsafe { sys::ImageRotate(&mut self.raw, degrees) }
+    }
+
+    /// Rotates the image 90 degrees clockwise.
+    fn rotate_cw / rotated_cw(&mut self) {
+        self.check_uncompressed();
+        unsafe { sys::ImageRotateCW(&mut self.raw) }
+    }
+
+    /// Rotates the image 90 degrees counter-clockwise.
+    fn rotate_ccw / rotated_ccw(&mut self) {
+        self.check_uncompressed();
+        unsafe { sys::ImageRotateCCW(&mut self.raw) }
+    }
+
+    fn flip_vertical / flipped_vertical(&mut self) {
+        self.check_uncompressed();
+        unsafe { sys::ImageFlipVertical(&mut self.raw) }
+    }
+
+    fn flip_horizontal / flipped_horizontal(&mut self) {
+        self.check_uncompressed();
+        unsafe { sys::ImageFlipHorizontal(&mut self.raw) }
+    }
+
+    /// Applies a box-approximated Gaussian blur with the given radius.
+    fn blur_gaussian / blurred_gaussian(&mut self, radius: u32) {
+        self.check_uncompressed();
+        let radius = radius.min(c_int::MAX as u32) as c_int;
+        unsafe { sys::ImageBlurGaussian(&mut self.raw, radius) }
+    }
+
+    /// Convolves the image with a square kernel, given row by row.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the kernel is not square with an odd side length, or if
+    /// the image is compressed.
+    fn convolve / convolved(&mut self, kernel: &[f32]) {
+        self.check_uncompressed();
+        let side = kernel.len().isqrt();
+        assert!(
+            side * side == kernel.len() && side % 2 == 1,
+            "convolution kernel has {} elements, expected an odd square such as 9 or 25",
+            kernel.len()
+        );
+        unsafe {
+            sys::ImageKernelConvolution(&mut self.raw, kernel.as_ptr(), kernel.len() as c_int)
+        }
+    }
+
+    /// Reduces the image to 16 bits per pixel with Floyd-Steinberg
+    /// dithering, using the given number of bits for each channel.
+    ///
+    /// The supported combinations, and the formats they produce, are:
+    ///
+    /// | Red | Green | Blue | Alpha | Format                              |
+    /// |-----|-------|------|-------|-------------------------------------|
+    /// | 5   | 6     | 5    | 0     | `PIXELFORMAT_UNCOMPRESSED_R5G6B5`   |
+    /// | 5   | 5     | 5    | 1     | `PIXELFORMAT_UNCOMPRESSED_R5G5B5A1` |
+    /// | 4   | 4     | 4    | 4     | `PIXELFORMAT_UNCOMPRESSED_R4G4B4A4` |
+    ///
+    /// # Panics
+    ///
+    /// Panics if the bit counts are not one of the combinations above, or
+    /// if the image is compressed.
+    fn dither / dithered(&mut self, r_bits: u8, g_bits: u8, b_bits: u8, a_bits: u8) {
+        self.check_uncompressed();
+        assert!(
+            matches!(
+                (r_bits, g_bits, b_bits, a_bits),
+                (5, 6, 5, 0) | (5, 5, 5, 1) | (4, 4, 4, 4)
+            ),
+            "dither bit depths {r_bits}/{g_bits}/{b_bits}/{a_bits} are not supported, \
+             expected 5/6/5/0, 5/5/5/1, or 4/4/4/4"
+        );
+        unsafe {
+            sys::ImageDither(
+                &mut self.raw,
+                c_int::from(r_bits),
+                c_int::from(g_bits),
+                c_int::from(b_bits),
+                c_int::from(a_bits),
+            )
+        }
+    }
+
+    /// Adjusts contrast by `contrast`, from -100 to 100.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `contrast` is out of range, or if the image is compressed.
+    fn contrast / contrasted(&mut self, contrast: f32) {
+        self.check_uncompressed();
+        assert!(
+            (-100.0..=100.0).contains(&contrast),
+            "contrast {contrast} is outside -100..=100"
+        );
+        unsafe { sys::ImageColorContrast(&mut self.raw, contrast) }
+    }
+
+    /// Adjusts brightness by `brightness`, from -255 to 255.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `brightness` is out of range, or if the image is
+    /// compressed.
+    fn brightness / brightened(&mut self, brightness: i32) {
+        self.check_uncompressed();
+        assert!(
+            (-255..=255).contains(&brightness),
+            "brightness {brightness} is outside -255..=255"
+        );
+        unsafe { sys::ImageColorBrightness(&mut self.raw, brightness) }
+    }
+
+    /// Multiplies every pixel by `color`.
+    fn tint / tinted(&mut self, color: Color) {
+        self.check_uncompressed();
+        unsafe { sys::ImageColorTint(&mut self.raw, color) }
+    }
+
+    fn invert / inverted(&mut self) {
+        self.check_uncompressed();
+        unsafe { sys::ImageColorInvert(&mut self.raw) }
+    }
+
+    fn grayscale / grayscaled(&mut self) {
+        self.check_uncompressed();
+        unsafe { sys::ImageColorGrayscale(&mut self.raw) }
+    }
+
+    /// Replaces every pixel of exactly `from` with `to`.
+    fn replace_color / color_replaced(&mut self, from: Color, to: Color) {
+        self.check_uncompressed();
+        unsafe { sys::ImageColorReplace(&mut self.raw, from, to) }
+    }
+
+    /// Snaps every pixel to the perceptually nearest color in `palette`,
+    /// keeping its alpha. The image is converted to R8G8B8A8 first, and an
+    /// empty palette leaves its colors unchanged.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the image is compressed.
+    fn quantize / quantized(&mut self, palette: &Palette) {
+        self.check_uncompressed();
+        self.convert(PixelFormat::PIXELFORMAT_UNCOMPRESSED_R8G8B8A8);
+
+        let nearest = palette.matcher();
+        let mut cache = HashMap::new();
+        let pixels = self
+            .pixels_mut::<Color>()
+            .expect("image was converted to R8G8B8A8");
+
+        for pixel in pixels {
+            let opaque = Color { a: 255, ..*pixel };
+            let index = *cache.entry(opaque).or_insert_with(|| nearest(opaque));
+
+            if let Some(index) = index {
+                *pixel = Color {
+                    a: pixel.a,
+                    ..palette.colors()[index]
+                };
+            }
+        }
+    }
+
+    /// Generates a full mipmap chain.
+    fn gen_mipmaps / mipmapped(&mut self) {
+        self.check_uncompressed();
+        unsafe { sys::ImageMipmaps(&mut self.raw) }
+    }
+
+    /// Grows the canvas to the next power-of-two size, filling new space
+    /// with `fill`.
+    fn expand_to_pot / expanded_to_pot(&mut self, fill: Color) {
+        self.check_uncompressed();
+        unsafe { sys::ImageToPOT(&mut self.raw, fill) }
+    }
+}
+
+impl OwnedImage {
+    pub fn is_compressed(&self) -> bool {
+        self.raw.format >= PixelFormat::PIXELFORMAT_COMPRESSED_DXT1_RGB as c_int
+    }
+
+    pub(super) fn check_uncompressed(&self) {
+        assert!(
+            !self.is_compressed(),
+            "image processing is not supported for compressed format {:?}",
+            self.format()
+        );
+    }
+}
+
+impl Clone for OwnedImage {
+    fn clone(&self) -> Self {
+        unsafe { Self::from_raw(sys::ImageCopy(self.raw)) }
+    }
+}
+
+fn check_size(width: i32, height: i32) {
+    assert!(
+        width > 0 && height > 0,
+        "image size {width}x{height} must be positive"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BLUE, RED};
+
+    fn compressed() -> OwnedImage {
+        OwnedImage::from_bytes(4, 4, PixelFormat::PIXELFORMAT_COMPRESSED_DXT1_RGB, &[0; 8])
+    }
+
+    fn rect(x: f32, y: f32, width: f32, height: f32) -> Rectangle {
+        Rectangle {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    #[test]
+    fn copies_leave_the_original_alone() {
+        let image = OwnedImage::new(4, 2, RED);
+        let resized = image.resized_nearest(2, 1).tinted(BLUE);
+
+        assert_eq!((image.width(), image.height()), (4, 2));
+        assert_eq!(image.get_pixel(0, 0), Some(RED));
+        assert_eq!((resized.width(), resized.height()), (2, 1));
+        assert_eq!(resized.mipmapped().mipmaps(), 2);
+    }
+
+    #[test]
+    fn crop_accepts_the_whole_image() {
+        let mut image = OwnedImage::new(4, 2, RED);
+        image
+            .crop(rect(0.0, 0.0, 4.0, 2.0))
+            .crop(rect(1.0, 1.0, 2.0, 1.0));
+
+        assert_eq!((image.width(), image.height()), (2, 1));
+    }
+
+    #[test]
+    #[should_panic(expected = "is outside the 4x2 image")]
+    fn crop_rejects_rectangles_past_the_edge() {
+        OwnedImage::new(4, 2, RED).crop(rect(3.0, 0.0, 2.0, 1.0));
+    }
+
+    #[test]
+    #[should_panic(expected = "is outside the 4x2 image")]
+    fn crop_rejects_negative_positions() {
+        OwnedImage::new(4, 2, RED).crop(rect(-1.0, 0.0, 2.0, 1.0));
+    }
+
+    #[test]
+    #[should_panic(expected = "is outside the 4x2 image")]
+    fn crop_rejects_empty_rectangles() {
+        OwnedImage::new(4, 2, RED).crop(rect(0.0, 0.0, 0.0, 1.0));
+    }
+
+    #[test]
+    fn convolve_accepts_odd_square_kernels() {
+        let mut image = OwnedImage::new(4, 4, RED);
+        let mut identity = [0.0; 9];
+        identity[4] = 1.0;
+
+        image.convolve(&identity).convolve(&[1.0]);
+
+        assert_eq!(image.get_pixel(1, 1), Some(RED));
+    }
+
+    #[test]
+    #[should_panic(expected = "convolution kernel has 8 elements")]
+    fn convolve_rejects_non_square_kernels() {
+        OwnedImage::new(4, 4, RED).convolve(&[0.0; 8]);
+    }
+
+    #[test]
+    #[should_panic(expected = "convolution kernel has 16 elements")]
+    fn convolve_rejects_even_kernels() {
+        OwnedImage::new(4, 4, RED).convolve(&[0.0; 16]);
+    }
+
+    #[test]
+    fn dither_produces_sixteen_bit_formats() {
+        let image = OwnedImage::new(2, 2, RED);
+
+        for (bits, format) in [
+            ((5, 6, 5, 0), PixelFormat::PIXELFORMAT_UNCOMPRESSED_R5G6B5),
+            ((5, 5, 5, 1), PixelFormat::PIXELFORMAT_UNCOMPRESSED_R5G5B5A1),
+            ((4, 4, 4, 4), PixelFormat::PIXELFORMAT_UNCOMPRESSED_R4G4B4A4),
+        ] {
+            let (r, g, b, a) = bits;
+            assert_eq!(image.dithered(r, g, b, a).format(), format);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "dither bit depths 8/8/8/8 are not supported")]
+    fn dither_rejects_other_bit_depths() {
+        OwnedImage::new(2, 2, RED).dither(8, 8, 8, 8);
+    }
+
+    #[test]
+    #[should_panic(expected = "dither bit depths 5/6/5/1 are not supported")]
+    fn dither_rejects_alpha_with_five_six_five() {
+        OwnedImage::new(2, 2, RED).dither(5, 6, 5, 1);
+    }
+
+    #[test]
+    fn contrast_and_brightness_accept_their_bounds() {
+        let mut image = OwnedImage::new(2, 2, RED);
+        image
+            .contrast(-100.0)
+            .contrast(100.0)
+            .brightness(-255)
+            .brightness(255);
+    }
+
+    #[test]
+    #[should_panic(expected = "contrast 100.5 is outside -100..=100")]
+    fn contrast_rejects_out_of_range() {
+        OwnedImage::new(2, 2, RED).contrast(100.5);
+    }
+
+    #[test]
+    #[should_panic(expected = "contrast NaN is outside -100..=100")]
+    fn contrast_rejects_nan() {
+        OwnedImage::new(2, 2, RED).contrast(f32::NAN);
+    }
+
+    #[test]
+    #[should_panic(expected = "brightness -256 is outside -255..=255")]
+    fn brightness_rejects_out_of_range() {
+        OwnedImage::new(2, 2, RED).brightness(-256);
+    }
+
+    #[test]
+    #[should_panic(expected = "image size 0x2 must be positive")]
+    fn resize_rejects_empty_sizes() {
+        OwnedImage::new(2, 2, RED).resize(0, 2);
+    }
+
+    #[test]
+    fn compressed_formats_are_detected() {
+        assert!(compressed().is_compressed());
+        assert!(!OwnedImage::new(1, 1, RED).is_compressed());
+    }
+
+    #[test]
+    #[should_panic(expected = "image processing is not supported for compressed format")]
+    fn processing_rejects_compressed_images() {
+        compressed().flip_vertical();
+    }
+
+    #[test]
+    #[should_panic(expected = "image processing is not supported for compressed format")]
+    fn convert_rejects_compressed_images() {
+        compressed().convert(PixelFormat::PIXELFORMAT_UNCOMPRESSED_R8G8B8A8);
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot convert to compressed format")]
+    fn convert_rejects_compressed_targets() {
+        OwnedImage::new(4, 4, RED).convert(PixelFormat::PIXELFORMAT_COMPRESSED_ETC2_RGB);
+    }
+
+    #[test]
+    #[should_panic(expected = "image processing is not supported for compressed format")]
+    fn alpha_mask_rejects_compressed_masks() {
+        OwnedImage::new(4, 4, RED).alpha_mask(&compressed());
+    }
+}
@@ -3,6 +3,48 @@ extern crate alloc;
 /// Unsafe bindings to raylib, raymath, rlgl, and raygui (if enabled)
 pub mod sys;
 
+mod error;
+mod log;
+#[cfg(feature = "rust_alloc")]
+mod memory;
+mod vector;
+
+pub use crate::error::{Error, ResourceKind, Result};
+
+/// Owned wave, sound, and music stream types
+pub mod audio;
+/// Safe 2D and 3D cameras and input-agnostic camera controllers
+pub mod camera;
+/// Color constructors, conversions, and blending, implemented in Rust
+pub mod color;
+/// Compute shaders and typed shader storage buffers
+#[cfg(feature = "opengl_43")]
+pub mod compute;
+/// Drawing shapes and text to the screen or to images
+pub mod draw;
+/// Owned GPU fonts, and fonts rasterized on the CPU for drawing onto images
+pub mod font;
+/// Owned CPU-side images with typed pixel access and processing
+pub mod image;
+/// Owned model type
+pub mod model;
+/// Recording the screen or a render texture to GIF or PNG files
+pub mod record;
+/// Owned shader type with typed, cached uniforms
+pub mod shader;
+/// Golden-image tests that render off-screen and compare against PNGs
+pub mod testing;
+/// Owned GPU textures, render textures, and animated textures
+pub mod texture;
+/// The application window, which owns the OpenGL context
+pub mod window;
+
+/// Safe wrappers around raylib's filesystem helpers
+pub mod fs;
+
+/// Seedable random numbers backed by raylib's generator
+pub mod random;
+
 use crate::sys::Color;
 
 pub const LIGHTGRAY: Color = Color {
@@ -0,0 +1,86 @@
+use crate::sys;
+use crate::sys::TraceLogLevel;
+use std::cell::RefCell;
+use std::ffi::CStr;
+use std::os::raw::{c_char, c_int};
+use std::sync::Once;
+
+/// The size of the buffer a single log line is formatted into.
+const MAX_MESSAGE_LENGTH: usize = 1024;
+
+static INSTALL: Once = Once::new();
+
+thread_local! {
+    // raylib invokes the callback on the thread that issued the call, so
+    // per-thread state keeps concurrent loaders from seeing each other's
+    // errors. Each active `capture` call owns one entry, innermost last.
+    static CAPTURES: RefCell<Vec<Vec<String>>> = const { RefCell::new(Vec::new()) };
+}
+
+unsafe extern "C" {
+    fn vsnprintf(
+        buf: *mut c_char,
+        size: usize,
+        format: *const c_char,
+        args: *mut sys::__va_list_tag,
+    ) -> c_int;
+}
+
+/// Installs the crate's trace-log callback if it hasn't been installed yet.
+///
+/// The callback prints messages the same way raylib's default logger does,
+/// and additionally hands warnings and errors to any active [`capture`] so
+/// that loaders can report why they failed.
+///
+/// raylib has no way to query the current callback, so one set by the user
+/// beforehand can't be chained to or restored; it is replaced for the rest
+/// of the process. This is documented on [`crate::Error`].
+pub(crate) fn install() {
+    INSTALL.call_once(|| unsafe { sys::SetTraceLogCallback(Some(trace_log)) });
+}
+
+/// Runs `f` and returns its result along with every warning and error
+/// raylib logged while it ran, oldest first.
+///
+/// Captures nest: an outer capture also sees messages logged during an
+/// inner one.
+pub(crate) fn capture<T>(f: impl FnOnce() -> T) -> (T, Vec<String>) {
+    install();
+    CAPTURES.with(|c| c.borrow_mut().push(Vec::new()));
+
+    let value = f();
+    let messages = CAPTURES.with(|c| c.borrow_mut().pop().unwrap_or_default());
+
+    (value, messages)
+}
+
+unsafe extern "C" fn trace_log(level: c_int, text: *const c_char, args: *mut sys::__va_list_tag) {
+    let mut buf = [0 as c_char; MAX_MESSAGE_LENGTH];
+    vsnprintf(buf.as_mut_ptr(), buf.len(), text, args);
+
+    let message = CStr::from_ptr(buf.as_ptr()).to_string_lossy();
+    let prefix = match level {
+        l if l == TraceLogLevel::LOG_TRACE as c_int => "TRACE",
+        l if l == TraceLogLevel::LOG_DEBUG as c_int => "DEBUG",
+        l if l == TraceLogLevel::LOG_INFO as c_int => "INFO",
+        l if l == TraceLogLevel::LOG_WARNING as c_int => "WARNING",
+        l if l == TraceLogLevel::LOG_ERROR as c_int => "ERROR",
+        l if l == TraceLogLevel::LOG_FATAL as c_int => "FATAL",
+        _ => "",
+    };
+
+    println!("{prefix}: {message}");
+
+    if level >= TraceLogLevel::LOG_WARNING as c_int {
+        CAPTURES.with(|c| {
+            for messages in c.borrow_mut().iter_mut() {
+                messages.push(message.to_string());
+            }
+        });
+    }
+
+    // raylib skips its own exit-on-fatal handling when a callback is set.
+    if level == TraceLogLevel::LOG_FATAL as c_int {
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,188 @@
+//! Allocation hooks that raylib, raygui, and Physac are compiled against when
+//! the `rust_alloc` feature is enabled.
+//!
+//! `build/alloc.h` redefines `RL_MALLOC`, `RL_CALLOC`, `RL_REALLOC`, and
+//! `RL_FREE` (and their raygui and Physac counterparts) to call the functions
+//! below, which forward to Rust's global allocator. C's allocation API does
+//! not pass the size back on free, so every block is prefixed with a header
+//! recording its total size.
+
+use std::alloc::{self, Layout};
+use std::os::raw::c_void;
+use std::ptr;
+
+/// Alignment of every block handed to C, matching `max_align_t`.
+const ALIGN: usize = 16;
+
+/// Size of the header stored in front of every block. It is a full
+/// alignment unit so the pointer returned to C stays aligned.
+const HEADER: usize = ALIGN;
+
+fn layout_for(size: usize) -> Option<Layout> {
+    let total = size.checked_add(HEADER)?;
+    Layout::from_size_align(total, ALIGN).ok()
+}
+
+unsafe fn finish(base: *mut u8, layout: Layout) -> *mut c_void {
+    if base.is_null() {
+        return ptr::null_mut();
+    }
+
+    base.cast::<usize>().write(layout.size());
+    base.add(HEADER).cast()
+}
+
+unsafe fn header_of(ptr: *mut c_void) -> (*mut u8, Layout) {
+    let base = ptr.cast::<u8>().sub(HEADER);
+    let total = base.cast::<usize>().read();
+
+    (base, Layout::from_size_align_unchecked(total, ALIGN))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rraylib_malloc(size: usize) -> *mut c_void {
+    match layout_for(size) {
+        Some(layout) => finish(alloc::alloc(layout), layout),
+        None => ptr::null_mut(),
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rraylib_calloc(count: usize, size: usize) -> *mut c_void {
+    match count.checked_mul(size).and_then(layout_for) {
+        Some(layout) => finish(alloc::alloc_zeroed(layout), layout),
+        None => ptr::null_mut(),
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rraylib_realloc(ptr: *mut c_void, size: usize) -> *mut c_void {
+    if ptr.is_null() {
+        return rraylib_malloc(size);
+    }
+
+    if size == 0 {
+        rraylib_free(ptr);
+        return ptr::null_mut();
+    }
+
+    let Some(new_layout) = layout_for(size) else {
+        return ptr::null_mut();
+    };
+
+    let (base, layout) = header_of(ptr);
+    finish(alloc::realloc(base, layout, new_layout.size()), new_layout)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rraylib_free(ptr: *mut c_void) {
+    if ptr.is_null() {
+        return;
+    }
+
+    let (base, layout) = header_of(ptr);
+    alloc::dealloc(base, layout);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_aligned(ptr: *mut c_void) -> bool {
+        ptr.cast::<u8>().align_offset(ALIGN) == 0
+    }
+
+    #[test]
+    fn malloc_is_aligned_and_writable() {
+        unsafe {
+            for size in [0, 1, 15, 16, 17, 4096] {
+                let ptr = rraylib_malloc(size);
+
+                assert!(!ptr.is_null());
+                assert!(is_aligned(ptr));
+                ptr::write_bytes(ptr.cast::<u8>(), 0xAB, size);
+
+                rraylib_free(ptr);
+            }
+        }
+    }
+
+    #[test]
+    fn calloc_zeroes() {
+        unsafe {
+            let ptr = rraylib_calloc(33, 7).cast::<u8>();
+
+            assert!(!ptr.is_null());
+            assert!(is_aligned(ptr.cast()));
+            assert!(std::slice::from_raw_parts(ptr, 33 * 7)
+                .iter()
+                .all(|&b| b == 0));
+
+            rraylib_free(ptr.cast());
+        }
+    }
+
+    #[test]
+    fn calloc_rejects_overflow() {
+        unsafe {
+            assert!(rraylib_calloc(usize::MAX, 2).is_null());
+            assert!(rraylib_malloc(usize::MAX).is_null());
+        }
+    }
+
+    #[test]
+    fn realloc_keeps_data_when_growing_and_shrinking() {
+        unsafe {
+            let ptr = rraylib_malloc(16).cast::<u8>();
+            for i in 0..16 {
+                ptr.add(i).write(i as u8);
+            }
+
+            let grown = rraylib_realloc(ptr.cast(), 1024).cast::<u8>();
+            assert!(!grown.is_null());
+            assert!(is_aligned(grown.cast()));
+            assert_eq!(
+                std::slice::from_raw_parts(grown, 16),
+                (0..16).collect::<Vec<u8>>()
+            );
+            ptr::write_bytes(grown.add(16), 0xFF, 1024 - 16);
+
+            let shrunk = rraylib_realloc(grown.cast(), 8).cast::<u8>();
+            assert!(!shrunk.is_null());
+            assert!(is_aligned(shrunk.cast()));
+            assert_eq!(
+                std::slice::from_raw_parts(shrunk, 8),
+                [0, 1, 2, 3, 4, 5, 6, 7]
+            );
+
+            rraylib_free(shrunk.cast());
+        }
+    }
+
+    #[test]
+    fn realloc_from_null_allocates() {
+        unsafe {
+            let ptr = rraylib_realloc(ptr::null_mut(), 64);
+
+            assert!(!ptr.is_null());
+            assert!(is_aligned(ptr));
+            ptr::write_bytes(ptr.cast::<u8>(), 1, 64);
+
+            rraylib_free(ptr);
+        }
+    }
+
+    #[test]
+    fn realloc_to_zero_frees() {
+        unsafe {
+            let ptr = rraylib_malloc(64);
+
+            assert!(rraylib_realloc(ptr, 0).is_null());
+        }
+    }
+
+    #[test]
+    fn free_null_is_a_no_op() {
+        unsafe { rraylib_free(ptr::null_mut()) };
+    }
+}
@@ -0,0 +1,48 @@
+use crate::error::{self, ResourceKind, Result};
+use crate::sys;
+use std::mem::ManuallyDrop;
+use std::path::Path;
+
+/// A 3D model with its meshes and materials, unloaded when dropped.
+#[derive(Debug)]
+pub struct OwnedModel {
+    raw: sys::Model,
+}
+
+impl OwnedModel {
+    /// Loads a model from a file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        error::load(
+            ResourceKind::Model,
+            path.as_ref(),
+            |path| Self {
+                raw: unsafe { sys::LoadModel(path.as_ptr()) },
+            },
+            |model| unsafe { sys::IsModelValid(model.raw) },
+        )
+    }
+
+    /// Takes ownership of a raw model.
+    ///
+    /// # Safety
+    ///
+    /// `raw` must be a valid model that is not unloaded anywhere else.
+    pub unsafe fn from_raw(raw: sys::Model) -> Self {
+        Self { raw }
+    }
+
+    /// Releases ownership of the model without unloading it.
+    pub fn into_raw(self) -> sys::Model {
+        ManuallyDrop::new(self).raw
+    }
+
+    pub fn as_raw(&self) -> &sys::Model {
+        &self.raw
+    }
+}
+
+impl Drop for OwnedModel {
+    fn drop(&mut self) {
+        unsafe { sys::UnloadModel(self.raw) }
+    }
+}
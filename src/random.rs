@@ -0,0 +1,207 @@
+use crate::sys;
+use std::marker::PhantomData;
+use std::ops::{Range, RangeInclusive};
+
+/// The number of distinct values drawn when producing floats.
+const FLOAT_STEPS: i32 = 1 << 24;
+
+/// A seedable random-number generator backed by raylib's internal RNG.
+///
+/// raylib keeps a single generator for the whole process, so every `Rng`
+/// shares (and advances) the same state. Seeding one `Rng` reseeds them all;
+/// this is what keeps sequences deterministic for a given seed regardless of
+/// whether values are drawn through raylib itself or through this type.
+///
+/// `Rng` is neither `Send` nor `Sync` because raylib's state is not
+/// synchronised.
+#[derive(Debug)]
+pub struct Rng {
+    _marker: PhantomData<*const ()>,
+}
+
+impl Rng {
+    /// Seeds raylib's generator and returns a handle to it.
+    pub fn new(seed: u32) -> Self {
+        let mut rng = Self::current();
+        rng.seed(seed);
+        rng
+    }
+
+    /// Returns a handle to raylib's generator without reseeding it.
+    pub fn current() -> Self {
+        Self {
+            _marker: PhantomData,
+        }
+    }
+
+    /// Reseeds raylib's generator.
+    pub fn seed(&mut self, seed: u32) {
+        unsafe { sys::SetRandomSeed(seed) }
+    }
+
+    /// Returns a random integer within `range`, inclusive on both ends.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is empty or spans `i32::MAX` or more values.
+    pub fn range(&mut self, range: RangeInclusive<i32>) -> i32 {
+        let (min, max) = checked_bounds(&range);
+        unsafe { sys::GetRandomValue(min, max) }
+    }
+
+    /// Returns `count` unique integers from `range` in random order, or
+    /// `None` if the range holds fewer than `count` values.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is empty or spans `i32::MAX` or more values.
+    pub fn sequence(&mut self, count: usize, range: RangeInclusive<i32>) -> Option<Vec<i32>> {
+        let (min, max) = checked_bounds(&range);
+
+        if count == 0 {
+            return Some(Vec::new());
+        }
+
+        if count as u64 > (max as i64 - min as i64 + 1) as u64 {
+            return None;
+        }
+
+        unsafe {
+            let ptr = sys::LoadRandomSequence(count as u32, min, max);
+
+            if ptr.is_null() {
+                return None;
+            }
+
+            let values = std::slice::from_raw_parts(ptr, count).to_vec();
+            sys::UnloadRandomSequence(ptr);
+            Some(values)
+        }
+    }
+
+    /// Shuffles a slice in place using a Fisher-Yates shuffle.
+    pub fn shuffle<T>(&mut self, slice: &mut [T]) {
+        for i in (1..slice.len()).rev() {
+            let j = self.range(0..=i as i32) as usize;
+            slice.swap(i, j);
+        }
+    }
+
+    /// Returns a random float in `[0, 1)`.
+    pub fn next_f32(&mut self) -> f32 {
+        self.range(0..=FLOAT_STEPS - 1) as f32 / FLOAT_STEPS as f32
+    }
+
+    /// Returns a random float within `range`.
+    pub fn range_f32(&mut self, range: Range<f32>) -> f32 {
+        range.start + (range.end - range.start) * self.next_f32()
+    }
+
+    /// Returns `true` with the given probability in `[0, 1]`.
+    pub fn chance(&mut self, probability: f32) -> bool {
+        self.next_f32() < probability
+    }
+
+    /// Returns a random `u32` covering the full range of the type.
+    pub fn next_u32(&mut self) -> u32 {
+        let hi = self.range(0..=0xFFFF) as u32;
+        let lo = self.range(0..=0xFFFF) as u32;
+        (hi << 16) | lo
+    }
+
+    /// Returns a random `u64` covering the full range of the type.
+    pub fn next_u64(&mut self) -> u64 {
+        let hi = self.next_u32() as u64;
+        let lo = self.next_u32() as u64;
+        (hi << 32) | lo
+    }
+}
+
+impl Default for Rng {
+    fn default() -> Self {
+        Self::current()
+    }
+}
+
+#[cfg(feature = "rand_core")]
+impl rand_core::RngCore for Rng {
+    fn next_u32(&mut self) -> u32 {
+        Rng::next_u32(self)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        Rng::next_u64(self)
+    }
+
+    fn fill_bytes(&mut self, dst: &mut [u8]) {
+        rand_core::impls::fill_bytes_via_next(self, dst)
+    }
+}
+
+#[cfg(feature = "rand_core")]
+impl rand_core::SeedableRng for Rng {
+    type Seed = [u8; 4];
+
+    fn from_seed(seed: Self::Seed) -> Self {
+        Self::new(u32::from_le_bytes(seed))
+    }
+}
+
+fn checked_bounds(range: &RangeInclusive<i32>) -> (i32, i32) {
+    let (min, max) = (*range.start(), *range.end());
+
+    assert!(min <= max, "random range is empty: {min}..={max}");
+    assert!(
+        (max as i64 - min as i64) < i32::MAX as i64,
+        "random range is too wide: {min}..={max}"
+    );
+
+    (min, max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // raylib's generator is global, so everything drawing from it stays in
+    // one test to keep parallel tests from interleaving.
+    #[test]
+    fn same_seed_gives_same_sequence() {
+        let draw = |rng: &mut Rng| {
+            let values: Vec<i32> = (0..32).map(|_| rng.range(-100..=100)).collect();
+            let floats: Vec<f32> = (0..8).map(|_| rng.next_f32()).collect();
+            let sequence = rng.sequence(10, 0..=9).unwrap();
+            let mut shuffled: Vec<u32> = (0..16).collect();
+            rng.shuffle(&mut shuffled);
+            (values, floats, sequence, shuffled, rng.next_u64())
+        };
+
+        let mut rng = Rng::new(1234);
+        let first = draw(&mut rng);
+
+        rng.seed(1234);
+        assert_eq!(draw(&mut rng), first);
+        assert_eq!(draw(&mut Rng::new(1234)), first);
+        assert_ne!(draw(&mut Rng::new(4321)), first);
+
+        let mut sorted = first.2.clone();
+        sorted.sort_unstable();
+        assert_eq!(sorted, (0..=9).collect::<Vec<_>>());
+
+        assert!(first.0.iter().all(|v| (-100..=100).contains(v)));
+        assert!(first.1.iter().all(|v| (0.0..1.0).contains(v)));
+        assert_eq!(rng.sequence(11, 0..=9), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "random range is empty")]
+    fn empty_range_panics() {
+        checked_bounds(&RangeInclusive::new(1, 0));
+    }
+
+    #[test]
+    #[should_panic(expected = "random range is too wide")]
+    fn wide_range_panics() {
+        checked_bounds(&(i32::MIN..=i32::MAX));
+    }
+}
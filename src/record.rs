@@ -0,0 +1,497 @@
+use crate::draw::DrawHandle;
+use crate::error::{Error, Result};
+use crate::image::OwnedImage;
+use crate::sys::PixelFormat;
+use crate::texture::OwnedRenderTexture;
+use std::collections::VecDeque;
+use std::io;
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+/// The files a [`Recorder`] writes.
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum RecordFormat {
+    /// A looping animated GIF at the output path. Requires the `gif` feature.
+    #[cfg(feature = "gif")]
+    Gif,
+    /// Numbered PNG files (`frame_00000.png`, `frame_00001.png`, ...) in the
+    /// output directory.
+    PngSequence,
+}
+
+/// Records frames from the screen or a render texture.
+///
+/// Frames are read back on the calling thread, which needs the OpenGL
+/// context, then scaled and encoded on a background thread so recording
+/// costs the game loop as little as possible.
+///
+/// ```ignore
+/// let mut recorder = Recorder::new("clip.gif", RecordFormat::Gif)
+///     .with_frame_skip(1)
+///     .with_scale(0.5)
+///     .with_keep_last(Duration::from_secs(10));
+///
+/// recorder.start()?;
+///
+/// while !window.should_close() {
+///     let mut frame = window.begin_drawing();
+///     // ...
+///     recorder.capture_screen(&mut frame, window.frame_time())?;
+/// }
+///
+/// recorder.stop()?;
+/// ```
+#[derive(Debug)]
+pub struct Recorder {
+    output: PathBuf,
+    format: RecordFormat,
+    frame_skip: u32,
+    scale: f32,
+    keep_last: Option<Duration>,
+    session: Option<Session>,
+}
+
+#[derive(Debug)]
+struct Session {
+    sender: Sender<Message>,
+    worker: JoinHandle<Result<()>>,
+    clock: FrameClock,
+}
+
+/// Decides which frames are captured and how long each is shown for.
+#[derive(Debug)]
+struct FrameClock {
+    frame_skip: u32,
+    skipped: u32,
+    pending_delay: Duration,
+}
+
+enum Message {
+    Frame(Frame),
+    Finish,
+}
+
+/// A captured frame in R8G8B8A8 format.
+struct Frame {
+    width: i32,
+    height: i32,
+    pixels: Vec<u8>,
+    delay: Duration,
+}
+
+impl Recorder {
+    /// Creates a recorder writing to `output`, which is a file for GIFs and
+    /// a directory for PNG sequences.
+    pub fn new(output: impl Into<PathBuf>, format: RecordFormat) -> Self {
+        Self {
+            output: output.into(),
+            format,
+            frame_skip: 0,
+            scale: 1.0,
+            keep_last: None,
+            session: None,
+        }
+    }
+
+    /// Records one frame out of every `skip + 1`, to save time and space.
+    pub fn with_frame_skip(mut self, skip: u32) -> Self {
+        self.frame_skip = skip;
+        self
+    }
+
+    /// Scales frames by `scale` before encoding, so 0.5 halves each side.
+    pub fn with_scale(mut self, scale: f32) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Keeps only the most recent `duration` of frames in memory, and
+    /// writes them when recording stops. Useful for attaching the moments
+    /// before a bug to a report.
+    pub fn with_keep_last(mut self, duration: Duration) -> Self {
+        self.keep_last = Some(duration);
+        self
+    }
+
+    /// Starts recording. Does nothing if already recording.
+    pub fn start(&mut self) -> Result<()> {
+        if self.session.is_some() {
+            return Ok(());
+        }
+
+        if self.format == RecordFormat::PngSequence {
+            std::fs::create_dir_all(&self.output)?;
+        }
+
+        let (sender, receiver) = mpsc::channel();
+        let worker = Worker {
+            output: self.output.clone(),
+            format: self.format,
+            scale: self.scale,
+            keep_last: self.keep_last,
+        };
+
+        let worker = thread::Builder::new()
+            .name("rraylib-recorder".to_owned())
+            .spawn(move || worker.run(receiver))?;
+
+        self.session = Some(Session {
+            sender,
+            worker,
+            clock: FrameClock::new(self.frame_skip),
+        });
+
+        Ok(())
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.session.is_some()
+    }
+
+    /// Captures the frame being drawn, including everything drawn to it so
+    /// far. `dt` is the frame time in seconds.
+    pub fn capture_screen(&mut self, frame: &mut DrawHandle<'_>, dt: f32) -> Result<()> {
+        let Some(delay) = self.due(dt) else {
+            return Ok(());
+        };
+
+        let image = frame.screenshot()?;
+        self.send(image, delay);
+
+        Ok(())
+    }
+
+    /// Captures the contents of a render texture. `dt` is the frame time in
+    /// seconds.
+    pub fn capture_texture(&mut self, target: &OwnedRenderTexture<'_>, dt: f32) -> Result<()> {
+        let Some(delay) = self.due(dt) else {
+            return Ok(());
+        };
+
+        let image = target.capture()?;
+        self.send(image, delay);
+
+        Ok(())
+    }
+
+    /// Stops recording and waits for every frame to be written.
+    ///
+    /// Returns the first error the encoder hit, if any. Does nothing if not
+    /// recording.
+    pub fn stop(&mut self) -> Result<()> {
+        let Some(session) = self.session.take() else {
+            return Ok(());
+        };
+
+        let _ = session.sender.send(Message::Finish);
+
+        session
+            .worker
+            .join()
+            .unwrap_or_else(|_| Err(Error::Io(io::Error::other("recorder thread panicked"))))
+    }
+
+    /// Returns the delay to record with if this frame should be captured.
+    fn due(&mut self, dt: f32) -> Option<Duration> {
+        self.session.as_mut()?.clock.tick(dt)
+    }
+
+    fn send(&mut self, image: OwnedImage, delay: Duration) {
+        let Some(session) = &self.session else {
+            return;
+        };
+
+        let image = image.converted(PixelFormat::PIXELFORMAT_UNCOMPRESSED_R8G8B8A8);
+        let frame = Frame {
+            width: image.width(),
+            height: image.height(),
+            pixels: image.as_bytes().to_vec(),
+            delay,
+        };
+
+        // If the worker has failed it has stopped listening; the error is
+        // reported by `stop`.
+        let _ = session.sender.send(Message::Frame(frame));
+    }
+}
+
+impl Drop for Recorder {
+    fn drop(&mut self) {
+        let _ = self.stop();
+    }
+}
+
+impl FrameClock {
+    fn new(frame_skip: u32) -> Self {
+        Self {
+            frame_skip,
+            skipped: 0,
+            pending_delay: Duration::ZERO,
+        }
+    }
+
+    /// Advances by `dt` seconds. Returns the delay to record with if this
+    /// frame should be captured, which covers every frame skipped since the
+    /// last one so the recording plays back in real time.
+    ///
+    /// Negative, NaN, and infinite frame times count as no time.
+    fn tick(&mut self, dt: f32) -> Option<Duration> {
+        let dt = Duration::try_from_secs_f32(dt).unwrap_or_default();
+        self.pending_delay = self.pending_delay.saturating_add(dt);
+
+        if self.skipped < self.frame_skip {
+            self.skipped += 1;
+            return None;
+        }
+
+        self.skipped = 0;
+        Some(std::mem::take(&mut self.pending_delay))
+    }
+}
+
+/// Appends `frame`, then drops the oldest frames until the rest last no
+/// longer than `keep`. The newest frame is always kept.
+fn push_within(buffer: &mut VecDeque<Frame>, frame: Frame, keep: Duration) {
+    buffer.push_back(frame);
+
+    while buffer.iter().map(|f| f.delay).sum::<Duration>() > keep && buffer.len() > 1 {
+        buffer.pop_front();
+    }
+}
+
+/// The background half of a recording session.
+struct Worker {
+    output: PathBuf,
+    format: RecordFormat,
+    scale: f32,
+    keep_last: Option<Duration>,
+}
+
+impl Worker {
+    fn run(self, receiver: Receiver<Message>) -> Result<()> {
+        let mut sink = Sink::new(self.format, self.output.clone());
+        let mut buffer = VecDeque::new();
+        let mut size = None;
+
+        // The loop also ends if the recorder is dropped without finishing.
+        while let Ok(Message::Frame(frame)) = receiver.recv() {
+            // Every frame is scaled to the size of the first, so a resized
+            // window doesn't break the output.
+            let size = *size.get_or_insert_with(|| {
+                (
+                    ((frame.width as f32 * self.scale) as i32).max(1),
+                    ((frame.height as f32 * self.scale) as i32).max(1),
+                )
+            });
+
+            let frame = resize(frame, size);
+
+            match self.keep_last {
+                Some(keep) => push_within(&mut buffer, frame, keep),
+                None => sink.write(frame)?,
+            }
+        }
+
+        for frame in buffer {
+            sink.write(frame)?;
+        }
+
+        sink.finish()
+    }
+}
+
+fn resize(frame: Frame, (width, height): (i32, i32)) -> Frame {
+    if (frame.width, frame.height) == (width, height) {
+        return frame;
+    }
+
+    let image = OwnedImage::from_bytes(
+        frame.width,
+        frame.height,
+        PixelFormat::PIXELFORMAT_UNCOMPRESSED_R8G8B8A8,
+        &frame.pixels,
+    )
+    .resized(width, height);
+
+    Frame {
+        width,
+        height,
+        pixels: image.as_bytes().to_vec(),
+        delay: frame.delay,
+    }
+}
+
+enum Sink {
+    Png {
+        dir: PathBuf,
+        index: u32,
+    },
+    #[cfg(feature = "gif")]
+    Gif {
+        path: PathBuf,
+        encoder: Option<gif::Encoder<io::BufWriter<std::fs::File>>>,
+    },
+}
+
+impl Sink {
+    fn new(format: RecordFormat, output: PathBuf) -> Self {
+        match format {
+            RecordFormat::PngSequence => Self::Png {
+                dir: output,
+                index: 0,
+            },
+            #[cfg(feature = "gif")]
+            RecordFormat::Gif => Self::Gif {
+                path: output,
+                encoder: None,
+            },
+        }
+    }
+
+    fn write(&mut self, frame: Frame) -> Result<()> {
+        match self {
+            Self::Png { dir, index } => {
+                let image = OwnedImage::from_bytes(
+                    frame.width,
+                    frame.height,
+                    PixelFormat::PIXELFORMAT_UNCOMPRESSED_R8G8B8A8,
+                    &frame.pixels,
+                );
+
+                image.export(dir.join(format!("frame_{index:05}.png")))?;
+                *index += 1;
+                Ok(())
+            }
+            #[cfg(feature = "gif")]
+            Self::Gif { path, encoder } => write_gif_frame(path, encoder, frame),
+        }
+    }
+
+    fn finish(self) -> Result<()> {
+        match self {
+            Self::Png { .. } => Ok(()),
+            #[cfg(feature = "gif")]
+            Self::Gif { encoder, .. } => {
+                if let Some(encoder) = encoder {
+                    let mut file = encoder.into_inner().map_err(gif_error)?;
+                    io::Write::flush(&mut file)?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(feature = "gif")]
+fn write_gif_frame(
+    path: &std::path::Path,
+    encoder: &mut Option<gif::Encoder<io::BufWriter<std::fs::File>>>,
+    mut frame: Frame,
+) -> Result<()> {
+    let too_large = |_| io::Error::new(io::ErrorKind::InvalidInput, "frame too large for a GIF");
+    let width = u16::try_from(frame.width).map_err(too_large)?;
+    let height = u16::try_from(frame.height).map_err(too_large)?;
+
+    let encoder = match encoder {
+        Some(encoder) => encoder,
+        None => {
+            let file = io::BufWriter::new(std::fs::File::create(path)?);
+            let mut new = gif::Encoder::new(file, width, height, &[]).map_err(gif_error)?;
+            new.set_repeat(gif::Repeat::Infinite).map_err(gif_error)?;
+            encoder.insert(new)
+        }
+    };
+
+    let mut gif_frame = gif::Frame::from_rgba_speed(width, height, &mut frame.pixels, 10);
+    // GIF delays are in hundredths of a second.
+    gif_frame.delay = (frame.delay.as_millis() / 10).clamp(1, u16::MAX as u128) as u16;
+
+    encoder.write_frame(&gif_frame).map_err(gif_error)
+}
+
+#[cfg(feature = "gif")]
+fn gif_error(error: gif::EncodingError) -> Error {
+    match error {
+        gif::EncodingError::Io(e) => Error::Io(e),
+        other => Error::Io(io::Error::other(other)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ticks(frame_skip: u32, dts: &[f32]) -> Vec<Option<u64>> {
+        let mut clock = FrameClock::new(frame_skip);
+
+        dts.iter()
+            .map(|&dt| clock.tick(dt).map(|delay| delay.as_millis() as u64))
+            .collect()
+    }
+
+    fn frame(millis: u64) -> Frame {
+        Frame {
+            width: 0,
+            height: 0,
+            pixels: Vec::new(),
+            delay: Duration::from_millis(millis),
+        }
+    }
+
+    fn kept(delays: &[u64], keep: u64) -> Vec<u64> {
+        let mut buffer = VecDeque::new();
+
+        for &delay in delays {
+            push_within(&mut buffer, frame(delay), Duration::from_millis(keep));
+        }
+
+        buffer.iter().map(|f| f.delay.as_millis() as u64).collect()
+    }
+
+    #[test]
+    fn every_frame_is_captured_without_skipping() {
+        assert_eq!(
+            ticks(0, &[0.125, 0.0, 0.5]),
+            [Some(125), Some(0), Some(500)]
+        );
+    }
+
+    #[test]
+    fn skipped_frames_add_to_the_next_delay() {
+        assert_eq!(
+            ticks(1, &[0.125, 0.125, 0.25, 0.5, 1.0]),
+            [None, Some(250), None, Some(750), None]
+        );
+        assert_eq!(
+            ticks(2, &[0.125, 0.25, 0.5, 1.0]),
+            [None, None, Some(875), None]
+        );
+    }
+
+    #[test]
+    fn invalid_frame_times_count_as_zero() {
+        assert_eq!(
+            ticks(
+                1,
+                &[f32::NAN, f32::INFINITY, -1.0, 0.25, 0.25, f32::NEG_INFINITY]
+            ),
+            [None, Some(0), None, Some(250), None, Some(250)]
+        );
+    }
+
+    #[test]
+    fn keep_last_drops_the_oldest_frames() {
+        assert_eq!(kept(&[100, 200, 300, 400], 700), [300, 400]);
+        assert_eq!(kept(&[100, 200, 300, 400], 1000), [100, 200, 300, 400]);
+        assert_eq!(kept(&[100, 100, 100, 100, 100], 250), [100, 100]);
+    }
+
+    #[test]
+    fn keep_last_always_keeps_the_newest_frame() {
+        assert_eq!(kept(&[100, 5000], 1000), [5000]);
+        assert_eq!(kept(&[100], 0), [100]);
+    }
+}
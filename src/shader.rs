@@ -0,0 +1,194 @@
+use crate::error::{self, Error, ResourceKind, Result};
+use crate::sys;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::ffi::CString;
+use std::io;
+use std::mem::ManuallyDrop;
+use std::path::Path;
+
+mod preprocess;
+mod reload;
+mod uniform;
+
+pub use preprocess::*;
+pub use reload::*;
+pub use uniform::*;
+
+/// Derives [`ShaderUniforms`] for a struct of uniform values.
+#[cfg(feature = "derive")]
+pub use rraylib_derive::ShaderUniforms;
+
+/// A group of uniforms uploaded together, such as a material's parameters.
+///
+/// Usually derived with `#[derive(ShaderUniforms)]` (requires the `derive`
+/// feature), which maps each field to the uniform of the same name.
+pub trait ShaderUniforms {
+    /// The resolved location of every uniform in the group.
+    type Locations;
+
+    /// Looks up the location of every uniform, failing if any is missing.
+    fn resolve(shader: &OwnedShader) -> Result<Self::Locations>;
+
+    /// Uploads every uniform to previously resolved locations.
+    fn apply_resolved(&self, shader: &OwnedShader, locations: &Self::Locations);
+
+    /// Resolves and uploads every uniform.
+    ///
+    /// Locations are cached by the shader, so only the first call performs
+    /// any lookups.
+    fn apply(&self, shader: &OwnedShader) -> Result<()> {
+        let locations = Self::resolve(shader)?;
+        self.apply_resolved(shader, &locations);
+        Ok(())
+    }
+}
+
+/// A compiled shader program that is unloaded when dropped.
+///
+/// Uniform locations are looked up by name the first time they are used and
+/// cached for the lifetime of the program.
+#[derive(Debug)]
+pub struct OwnedShader {
+    raw: sys::Shader,
+    locations: RefCell<HashMap<String, i32>>,
+}
+
+impl OwnedShader {
+    /// Loads and compiles a shader from vertex and/or fragment source files.
+    ///
+    /// A missing stage falls back to raylib's default shader for that stage.
+    /// A source file that doesn't exist or doesn't compile is an error,
+    /// rather than silently falling back to the default program as raylib
+    /// does.
+    pub fn load(vs_path: Option<&Path>, fs_path: Option<&Path>) -> Result<Self> {
+        for path in [vs_path, fs_path].into_iter().flatten() {
+            if !path.is_file() {
+                return Err(Error::Load {
+                    kind: ResourceKind::Shader,
+                    path: path.to_path_buf(),
+                    message: Some("file not found".to_owned()),
+                });
+            }
+        }
+
+        let vs = vs_path.map(crate::fs::to_cstring).transpose()?;
+        let fs = fs_path.map(crate::fs::to_cstring).transpose()?;
+        let path = fs_path.or(vs_path).unwrap_or(Path::new(""));
+        let custom = vs.is_some() || fs.is_some();
+
+        error::load(
+            ResourceKind::Shader,
+            path,
+            |_| unsafe { Self::from_raw(sys::LoadShader(c_ptr(&vs), c_ptr(&fs))) },
+            |shader| shader.is_valid(custom),
+        )
+    }
+
+    /// Compiles a shader from vertex and/or fragment source code.
+    ///
+    /// A missing stage falls back to raylib's default shader for that stage.
+    /// Source that doesn't compile is an error, rather than silently falling
+    /// back to the default program as raylib does.
+    pub fn load_from_memory(vs_code: Option<&str>, fs_code: Option<&str>) -> Result<Self> {
+        let to_cstring = |code: &str| {
+            CString::new(code)
+                .map_err(|e| Error::Io(io::Error::new(io::ErrorKind::InvalidInput, e)))
+        };
+        let vs = vs_code.map(to_cstring).transpose()?;
+        let fs = fs_code.map(to_cstring).transpose()?;
+        let custom = vs.is_some() || fs.is_some();
+
+        error::load(
+            ResourceKind::Shader,
+            Path::new("<memory>"),
+            |_| unsafe { Self::from_raw(sys::LoadShaderFromMemory(c_ptr(&vs), c_ptr(&fs))) },
+            |shader| shader.is_valid(custom),
+        )
+    }
+
+    /// Takes ownership of a raw shader.
+    ///
+    /// # Safety
+    ///
+    /// `raw` must be a valid shader that is not unloaded anywhere else.
+    pub unsafe fn from_raw(raw: sys::Shader) -> Self {
+        Self {
+            raw,
+            locations: RefCell::default(),
+        }
+    }
+
+    /// Releases ownership of the shader without unloading it.
+    pub fn into_raw(self) -> sys::Shader {
+        ManuallyDrop::new(self).raw
+    }
+
+    pub fn as_raw(&self) -> &sys::Shader {
+        &self.raw
+    }
+
+    /// Returns the location of a uniform, looking it up on first use.
+    pub fn location(&self, name: &str) -> Result<i32> {
+        if let Some(&location) = self.locations.borrow().get(name) {
+            return location_result(name, location);
+        }
+
+        let c_name = CString::new(name).map_err(|_| Error::UniformNotFound(name.to_owned()))?;
+        let location = unsafe { sys::GetShaderLocation(self.raw, c_name.as_ptr()) };
+        self.locations
+            .borrow_mut()
+            .insert(name.to_owned(), location);
+
+        location_result(name, location)
+    }
+
+    /// Sets a uniform by name.
+    pub fn set<U: ShaderUniform>(&self, name: &str, value: U) -> Result<()> {
+        let location = self.location(name)?;
+        self.set_at(location, value);
+        Ok(())
+    }
+
+    /// Sets a uniform at a location previously returned by [`Self::location`].
+    pub fn set_at<U: ShaderUniform>(&self, location: i32, value: U) {
+        value.set_uniform(self.raw, location);
+    }
+
+    /// Whether raylib produced a usable program. When compiling a custom
+    /// stage fails, raylib hands back its default program instead, which
+    /// only counts as valid if no custom stage was requested.
+    fn is_valid(&self, custom: bool) -> bool {
+        unsafe {
+            sys::IsShaderValid(self.raw) && !(custom && self.raw.id == sys::rlGetShaderIdDefault())
+        }
+    }
+
+    /// Resolves every uniform name `other` has looked up against this
+    /// shader, so a replacement program starts with a warm cache.
+    pub(crate) fn resolve_like(&self, other: &OwnedShader) {
+        for name in other.locations.borrow().keys() {
+            let _ = self.location(name);
+        }
+    }
+}
+
+impl Drop for OwnedShader {
+    fn drop(&mut self) {
+        if self.raw.id != 0 {
+            unsafe { sys::UnloadShader(self.raw) }
+        }
+    }
+}
+
+fn location_result(name: &str, location: i32) -> Result<i32> {
+    if location < 0 {
+        Err(Error::UniformNotFound(name.to_owned()))
+    } else {
+        Ok(location)
+    }
+}
+
+fn c_ptr(s: &Option<CString>) -> *const std::os::raw::c_char {
+    s.as_ref().map_or(std::ptr::null(), |s| s.as_ptr())
+}
@@ -0,0 +1,661 @@
+use super::OwnedShader;
+use crate::error::{Error, Result};
+use crate::sys;
+use std::borrow::Cow;
+use std::collections::HashSet;
+use std::ffi::CStr;
+use std::fmt::Write;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// The stage a piece of shader source is compiled for.
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum ShaderStage {
+    Vertex,
+    Fragment,
+}
+
+/// A GLSL dialect, corresponding to one of the crate's OpenGL features.
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum GlslTarget {
+    /// `#version 430`, for `opengl_43`.
+    Glsl430,
+    /// `#version 330`, for `opengl_33`.
+    Glsl330,
+    /// `#version 120`, for `opengl_21`.
+    Glsl120,
+    /// `#version 110`, for `opengl_11`.
+    Glsl110,
+    /// `#version 300 es`, for `opengl_es_30`.
+    Glsl300Es,
+    /// `#version 100`, for `opengl_es_20`.
+    Glsl100Es,
+}
+
+impl GlslTarget {
+    /// The dialect matching the OpenGL feature this crate was built with.
+    ///
+    /// When several features are enabled this follows the build script,
+    /// where the last version it applies wins, except that `opengl_43`
+    /// takes precedence over the default `opengl_33`.
+    pub const fn current() -> Self {
+        if cfg!(feature = "opengl_es_20") {
+            Self::Glsl100Es
+        } else if cfg!(feature = "opengl_es_30") {
+            Self::Glsl300Es
+        } else if cfg!(feature = "opengl_11") {
+            Self::Glsl110
+        } else if cfg!(feature = "opengl_21") {
+            Self::Glsl120
+        } else if cfg!(feature = "opengl_43") {
+            Self::Glsl430
+        } else {
+            Self::Glsl330
+        }
+    }
+
+    /// The `#version` directive for this dialect.
+    pub const fn version_directive(&self) -> &'static str {
+        match self {
+            Self::Glsl430 => "#version 430",
+            Self::Glsl330 => "#version 330",
+            Self::Glsl120 => "#version 120",
+            Self::Glsl110 => "#version 110",
+            Self::Glsl300Es => "#version 300 es",
+            Self::Glsl100Es => "#version 100",
+        }
+    }
+
+    pub const fn is_es(&self) -> bool {
+        matches!(self, Self::Glsl300Es | Self::Glsl100Es)
+    }
+
+    /// Whether the dialect uses `in`/`out` and `texture()` rather than
+    /// `attribute`/`varying` and `texture2D()`.
+    pub const fn is_modern(&self) -> bool {
+        matches!(self, Self::Glsl430 | Self::Glsl330 | Self::Glsl300Es)
+    }
+
+    /// The compatibility macros injected after the `#version` directive.
+    ///
+    /// Shaders written against these macros compile unchanged on every
+    /// dialect:
+    ///
+    /// | Macro         | Modern GLSL        | Legacy GLSL     |
+    /// |---------------|--------------------|-----------------|
+    /// | `ATTRIBUTE`   | `in`               | `attribute`     |
+    /// | `VARYING_OUT` | `out`              | `varying`       |
+    /// | `VARYING_IN`  | `in`               | `varying`       |
+    /// | `TEXTURE2D`   | `texture`          | `texture2D`     |
+    /// | `FRAG_COLOR`  | a declared `out`   | `gl_FragColor`  |
+    ///
+    /// `GLSL_VERSION` is defined to the numeric version, and `GLSL_ES` is
+    /// defined on OpenGL ES targets.
+    ///
+    /// On modern dialects the fragment output behind `FRAG_COLOR` is only
+    /// declared if `source` uses `FRAG_COLOR`, so shaders that declare
+    /// their own output don't end up with two.
+    pub fn compatibility_header(&self, stage: ShaderStage, source: &str) -> String {
+        let mut header = String::new();
+        let version = match self {
+            Self::Glsl430 => 430,
+            Self::Glsl330 => 330,
+            Self::Glsl120 => 120,
+            Self::Glsl110 => 110,
+            Self::Glsl300Es => 300,
+            Self::Glsl100Es => 100,
+        };
+
+        let _ = writeln!(header, "#define GLSL_VERSION {version}");
+
+        if self.is_es() {
+            header.push_str("#define GLSL_ES 1\n");
+
+            if stage == ShaderStage::Fragment {
+                header.push_str("precision mediump float;\n");
+            }
+        }
+
+        if self.is_modern() {
+            header.push_str("#define ATTRIBUTE in\n");
+            header.push_str("#define VARYING_OUT out\n");
+            header.push_str("#define VARYING_IN in\n");
+            header.push_str("#define TEXTURE2D texture\n");
+
+            if stage == ShaderStage::Fragment && uses_identifier(source, "FRAG_COLOR") {
+                header.push_str("out vec4 rl_FragColor;\n");
+                header.push_str("#define FRAG_COLOR rl_FragColor\n");
+            }
+        } else {
+            header.push_str("#define ATTRIBUTE attribute\n");
+            header.push_str("#define VARYING_OUT varying\n");
+            header.push_str("#define VARYING_IN varying\n");
+            header.push_str("#define TEXTURE2D texture2D\n");
+            header.push_str("#define FRAG_COLOR gl_FragColor\n");
+        }
+
+        header
+    }
+}
+
+/// Locates and reads the files named by `#include` directives.
+pub trait IncludeResolver {
+    /// Reads `name` as included from `from` (or from the top level when
+    /// `None`), returning the resolved path and its contents.
+    fn resolve(&self, name: &str, from: Option<&Path>) -> io::Result<(PathBuf, String)>;
+}
+
+/// Resolves includes relative to the including file, then through a list
+/// of search directories, using raylib's `LoadFileText`.
+///
+/// Because raylib's file callbacks (`SetLoadFileTextCallback`) apply, this
+/// also reads from any virtual filesystem installed through them.
+#[derive(Debug, Clone, Default)]
+pub struct FileResolver {
+    pub search_dirs: Vec<PathBuf>,
+}
+
+impl FileResolver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_search_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.search_dirs.push(dir.into());
+        self
+    }
+}
+
+impl IncludeResolver for FileResolver {
+    fn resolve(&self, name: &str, from: Option<&Path>) -> io::Result<(PathBuf, String)> {
+        let relative = from.and_then(Path::parent).map(|dir| dir.join(name));
+        let candidates = relative
+            .into_iter()
+            .chain(self.search_dirs.iter().map(|dir| dir.join(name)));
+
+        for candidate in candidates {
+            if let Some(text) = load_file_text(&candidate)? {
+                return Ok((candidate, text));
+            }
+        }
+
+        Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("include not found: {name}"),
+        ))
+    }
+}
+
+fn load_file_text(path: &Path) -> io::Result<Option<String>> {
+    let c_path = crate::fs::to_cstring(path)?;
+
+    unsafe {
+        let text = sys::LoadFileText(c_path.as_ptr());
+
+        if text.is_null() {
+            return Ok(None);
+        }
+
+        let owned = CStr::from_ptr(text).to_string_lossy().into_owned();
+        sys::UnloadFileText(text);
+        Ok(Some(owned))
+    }
+}
+
+/// Rewrites GLSL source before it is handed to `LoadShaderFromMemory`.
+///
+/// The preprocessor expands `#include "file"` directives (honouring
+/// `#pragma once`), prepends the `#version` directive and compatibility
+/// macros for the build's GL feature, and injects user `#define`s. Any
+/// `#version` directive already in the source is replaced, and `#extension`
+/// directives at the top of the source are moved above the header.
+pub struct ShaderPreprocessor {
+    target: GlslTarget,
+    defines: Vec<(String, String)>,
+    resolver: Box<dyn IncludeResolver>,
+}
+
+impl ShaderPreprocessor {
+    pub fn new() -> Self {
+        Self {
+            target: GlslTarget::current(),
+            defines: Vec::new(),
+            resolver: Box::new(FileResolver::new()),
+        }
+    }
+
+    /// Overrides the GLSL dialect, which defaults to [`GlslTarget::current`].
+    pub fn target(mut self, target: GlslTarget) -> Self {
+        self.target = target;
+        self
+    }
+
+    /// Adds `#define name value`.
+    pub fn define(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.defines.push((name.into(), value.into()));
+        self
+    }
+
+    pub fn resolver(mut self, resolver: impl IncludeResolver + 'static) -> Self {
+        self.resolver = Box::new(resolver);
+        self
+    }
+
+    /// Preprocesses in-memory source. Relative includes are resolved
+    /// against `origin` if given.
+    pub fn process(
+        &self,
+        stage: ShaderStage,
+        source: &str,
+        origin: Option<&Path>,
+    ) -> Result<String> {
+        let mut state = State {
+            stack: Vec::new(),
+            once: HashSet::new(),
+        };
+
+        let (extensions, source) = split_extensions(source);
+        let mut body = String::new();
+        self.expand(&mut body, &source, origin, &mut state)?;
+
+        let mut out = String::new();
+        out.push_str(self.target.version_directive());
+        out.push('\n');
+
+        // `#extension` has to come before any declarations, including the
+        // header's `precision` and `out` ones.
+        for extension in extensions {
+            out.push_str(extension);
+            out.push('\n');
+        }
+
+        out.push_str(&self.target.compatibility_header(stage, &body));
+
+        for (name, value) in &self.defines {
+            let _ = writeln!(out, "#define {name} {value}");
+        }
+
+        out.push_str(&body);
+        Ok(out)
+    }
+
+    /// Reads and preprocesses a source file.
+    pub fn process_file(&self, stage: ShaderStage, path: impl AsRef<Path>) -> Result<String> {
+        let path = path.as_ref();
+        let source = load_file_text(path)?.ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("shader source not found: {}", path.display()),
+            )
+        })?;
+
+        self.process(stage, &source, Some(path))
+    }
+
+    /// Preprocesses vertex and/or fragment source files and compiles them.
+    pub fn load(&self, vs_path: Option<&Path>, fs_path: Option<&Path>) -> Result<OwnedShader> {
+        let vs = vs_path
+            .map(|path| self.process_file(ShaderStage::Vertex, path))
+            .transpose()?;
+        let fs = fs_path
+            .map(|path| self.process_file(ShaderStage::Fragment, path))
+            .transpose()?;
+
+        OwnedShader::load_from_memory(vs.as_deref(), fs.as_deref())
+    }
+
+    fn expand(
+        &self,
+        out: &mut String,
+        source: &str,
+        path: Option<&Path>,
+        state: &mut State,
+    ) -> Result<()> {
+        let display = path.map_or_else(|| PathBuf::from("<memory>"), Path::to_path_buf);
+
+        if let Some(path) = path {
+            if state.once.contains(path) {
+                return Ok(());
+            }
+
+            if state.stack.iter().any(|p| p == path) {
+                return Err(Error::Preprocess {
+                    path: display,
+                    line: 0,
+                    message: "recursive #include".to_owned(),
+                });
+            }
+
+            state.stack.push(path.to_path_buf());
+        }
+
+        let _ = writeln!(out, "#line 1");
+
+        for (index, line) in source.lines().enumerate() {
+            let line_number = index + 1;
+            let directive = line.trim_start();
+
+            if directive.starts_with("#version") {
+                out.push('\n');
+                continue;
+            }
+
+            if let Some(rest) = directive.strip_prefix("#pragma") {
+                if rest.trim() == "once" {
+                    if let Some(path) = path {
+                        state.once.insert(path.to_path_buf());
+                    }
+
+                    out.push('\n');
+                    continue;
+                }
+            }
+
+            if let Some(rest) = directive.strip_prefix("#include") {
+                let name = parse_include(rest).ok_or_else(|| Error::Preprocess {
+                    path: display.clone(),
+                    line: line_number,
+                    message: format!("malformed #include: {}", rest.trim()),
+                })?;
+
+                let (included, text) =
+                    self.resolver
+                        .resolve(name, path)
+                        .map_err(|e| Error::Preprocess {
+                            path: display.clone(),
+                            line: line_number,
+                            message: e.to_string(),
+                        })?;
+
+                self.expand(out, &text, Some(&included), state)?;
+                let _ = writeln!(out, "#line {}", line_number + 1);
+                continue;
+            }
+
+            out.push_str(line);
+            out.push('\n');
+        }
+
+        if path.is_some() {
+            state.stack.pop();
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for ShaderPreprocessor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for ShaderPreprocessor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ShaderPreprocessor")
+            .field("target", &self.target)
+            .field("defines", &self.defines)
+            .finish_non_exhaustive()
+    }
+}
+
+struct State {
+    /// Files currently being expanded, to detect include cycles.
+    stack: Vec<PathBuf>,
+    /// Files that contained `#pragma once` and have already been expanded.
+    once: HashSet<PathBuf>,
+}
+
+/// Takes the `#extension` directives out of the top of `source`, before
+/// any other code, leaving blank lines in their place so line numbers are
+/// unchanged.
+fn split_extensions(source: &str) -> (Vec<&str>, Cow<'_, str>) {
+    let mut extensions = Vec::new();
+    let mut rest = String::new();
+
+    for (index, line) in source.lines().enumerate() {
+        let directive = line.trim_start();
+
+        if directive.starts_with("#extension") {
+            extensions.push(directive);
+            rest.push('\n');
+        } else if directive.is_empty()
+            || directive.starts_with("//")
+            || directive.starts_with("#version")
+        {
+            rest.push_str(line);
+            rest.push('\n');
+        } else {
+            if extensions.is_empty() {
+                return (extensions, Cow::Borrowed(source));
+            }
+
+            for line in source.lines().skip(index) {
+                rest.push_str(line);
+                rest.push('\n');
+            }
+
+            break;
+        }
+    }
+
+    (extensions, Cow::Owned(rest))
+}
+
+/// Whether `source` contains `name` as a whole identifier.
+fn uses_identifier(source: &str, name: &str) -> bool {
+    let is_ident = |c: char| c.is_ascii_alphanumeric() || c == '_';
+
+    source.match_indices(name).any(|(start, _)| {
+        let before = source[..start].chars().next_back();
+        let after = source[start + name.len()..].chars().next();
+        !before.is_some_and(is_ident) && !after.is_some_and(is_ident)
+    })
+}
+
+fn parse_include(rest: &str) -> Option<&str> {
+    let rest = rest.trim();
+    let (open, close) = match rest.chars().next()? {
+        '"' => ('"', '"'),
+        '<' => ('<', '>'),
+        _ => return None,
+    };
+
+    let inner = rest.strip_prefix(open)?;
+    let end = inner.find(close)?;
+    Some(&inner[..end])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    /// Serves includes from memory, keyed by name.
+    struct MemoryResolver(HashMap<&'static str, &'static str>);
+
+    impl IncludeResolver for MemoryResolver {
+        fn resolve(&self, name: &str, _from: Option<&Path>) -> io::Result<(PathBuf, String)> {
+            self.0
+                .get(name)
+                .map(|text| (PathBuf::from(name), text.to_string()))
+                .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, name.to_owned()))
+        }
+    }
+
+    fn preprocessor(files: &[(&'static str, &'static str)]) -> ShaderPreprocessor {
+        ShaderPreprocessor::new()
+            .target(GlslTarget::Glsl330)
+            .resolver(MemoryResolver(files.iter().copied().collect()))
+    }
+
+    /// The expanded source, which starts at the first `#line`.
+    fn body(output: &str) -> &str {
+        &output[output.find("#line 1").unwrap()..]
+    }
+
+    #[test]
+    fn includes_are_expanded_with_line_directives() {
+        let output = preprocessor(&[("common.glsl", "float half(float x) { return x / 2.0; }")])
+            .process(
+                ShaderStage::Vertex,
+                "#version 100\nvoid a() {}\n#include \"common.glsl\"\nvoid b() {}",
+                None,
+            )
+            .unwrap();
+
+        assert!(output.starts_with("#version 330\n"));
+        assert_eq!(output.matches("#version").count(), 1);
+        assert_eq!(
+            body(&output),
+            "#line 1\n\nvoid a() {}\n#line 1\nfloat half(float x) { return x / 2.0; }\n#line 4\nvoid b() {}\n"
+        );
+    }
+
+    #[test]
+    fn nested_includes_restore_each_line() {
+        let output = preprocessor(&[
+            (
+                "outer.glsl",
+                "// outer\n#include <inner.glsl>\n// outer end",
+            ),
+            ("inner.glsl", "// inner"),
+        ])
+        .process(
+            ShaderStage::Vertex,
+            "#include \"outer.glsl\"\n// main",
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(
+            body(&output),
+            "#line 1\n#line 1\n// outer\n#line 1\n// inner\n#line 3\n// outer end\n#line 2\n// main\n"
+        );
+    }
+
+    #[test]
+    fn pragma_once_includes_a_file_once() {
+        let output = preprocessor(&[("once.glsl", "#pragma once\nconst float ONCE = 1.0;")])
+            .process(
+                ShaderStage::Vertex,
+                "#include \"once.glsl\"\n#include \"once.glsl\"",
+                None,
+            )
+            .unwrap();
+
+        assert_eq!(output.matches("const float ONCE").count(), 1);
+    }
+
+    #[test]
+    fn include_cycles_are_errors() {
+        let error = preprocessor(&[
+            ("a.glsl", "#include \"b.glsl\""),
+            ("b.glsl", "#include \"a.glsl\""),
+        ])
+        .process(ShaderStage::Vertex, "#include \"a.glsl\"", None)
+        .unwrap_err();
+
+        assert!(matches!(
+            error,
+            Error::Preprocess { ref path, line: 0, ref message }
+                if path == Path::new("a.glsl") && message == "recursive #include"
+        ));
+    }
+
+    #[test]
+    fn include_errors_report_the_line() {
+        let error = preprocessor(&[])
+            .process(
+                ShaderStage::Vertex,
+                "void a() {}\n#include \"missing.glsl\"",
+                None,
+            )
+            .unwrap_err();
+
+        assert!(matches!(error, Error::Preprocess { line: 2, .. }));
+        assert_eq!(error.to_string(), "<memory>:2: missing.glsl");
+
+        let error = preprocessor(&[])
+            .process(ShaderStage::Vertex, "#include missing.glsl", None)
+            .unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "<memory>:1: malformed #include: missing.glsl"
+        );
+    }
+
+    #[test]
+    fn frag_color_output_is_only_declared_when_used() {
+        let uses = "void main() { FRAG_COLOR = vec4(1.0); }";
+        let declares = "out vec4 finalColor;\nvoid main() { finalColor = vec4(1.0); }";
+        let target = GlslTarget::Glsl330;
+
+        assert!(target
+            .compatibility_header(ShaderStage::Fragment, uses)
+            .contains("out vec4 rl_FragColor;"));
+        assert!(!target
+            .compatibility_header(ShaderStage::Fragment, declares)
+            .contains("rl_FragColor"));
+        assert!(!target
+            .compatibility_header(ShaderStage::Fragment, "vec4 MY_FRAG_COLOR_2;")
+            .contains("rl_FragColor"));
+        assert!(!target
+            .compatibility_header(ShaderStage::Vertex, uses)
+            .contains("rl_FragColor"));
+        assert!(GlslTarget::Glsl100Es
+            .compatibility_header(ShaderStage::Fragment, declares)
+            .contains("#define FRAG_COLOR gl_FragColor"));
+
+        let output = preprocessor(&[("color.glsl", "#define WRITE(c) FRAG_COLOR = c")])
+            .process(ShaderStage::Fragment, "#include \"color.glsl\"", None)
+            .unwrap();
+
+        assert!(output.contains("out vec4 rl_FragColor;"));
+    }
+
+    #[test]
+    fn defines_follow_the_header() {
+        let output = preprocessor(&[])
+            .define("LIGHTS", "4")
+            .process(ShaderStage::Vertex, "void main() {}", None)
+            .unwrap();
+
+        let define = output.find("#define LIGHTS 4\n").unwrap();
+        assert!(define > output.find("#define GLSL_VERSION 330").unwrap());
+        assert!(define < output.find("void main").unwrap());
+    }
+
+    #[test]
+    fn extensions_come_before_the_header() {
+        let output = preprocessor(&[])
+            .target(GlslTarget::Glsl300Es)
+            .process(
+                ShaderStage::Fragment,
+                "#version 300 es\n// blur\n#extension GL_OES_standard_derivatives : enable\nvoid main() { FRAG_COLOR = vec4(dFdx(1.0)); }",
+                None,
+            )
+            .unwrap();
+
+        assert!(output.starts_with(
+            "#version 300 es\n#extension GL_OES_standard_derivatives : enable\n#define GLSL_VERSION 300\n"
+        ));
+        assert_eq!(output.matches("#extension").count(), 1);
+        assert!(output.find("precision mediump").unwrap() > output.find("#extension").unwrap());
+        assert!(output.contains("out vec4 rl_FragColor;"));
+        assert_eq!(
+            body(&output),
+            "#line 1\n\n// blur\n\nvoid main() { FRAG_COLOR = vec4(dFdx(1.0)); }\n"
+        );
+    }
+
+    #[test]
+    fn later_extensions_are_left_in_place() {
+        let source = "void a() {}\n#extension GL_EXT_foo : enable";
+        let (extensions, rest) = split_extensions(source);
+
+        assert!(extensions.is_empty());
+        assert_eq!(rest, source);
+    }
+}
@@ -0,0 +1,124 @@
+use super::OwnedShader;
+use crate::error::Result;
+use crate::log;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// A shader that recompiles itself when its source files change on disk.
+///
+/// Call [`Self::poll`] once per frame. When either source file's
+/// modification time changes the program is recompiled, and only swapped in
+/// if compilation succeeds; on failure the previous program stays active and
+/// the compiler output is kept in [`Self::error_log`].
+#[derive(Debug)]
+pub struct ReloadableShader {
+    vs_path: Option<PathBuf>,
+    fs_path: Option<PathBuf>,
+    shader: OwnedShader,
+    mod_times: [Option<SystemTime>; 2],
+    generation: u64,
+    error_log: Vec<String>,
+}
+
+impl ReloadableShader {
+    /// Loads and compiles a shader from vertex and/or fragment source files.
+    pub fn load(vs_path: Option<&Path>, fs_path: Option<&Path>) -> Result<Self> {
+        let shader = OwnedShader::load(vs_path, fs_path)?;
+
+        let mut this = Self {
+            vs_path: vs_path.map(Path::to_path_buf),
+            fs_path: fs_path.map(Path::to_path_buf),
+            shader,
+            mod_times: [None, None],
+            generation: 0,
+            error_log: Vec::new(),
+        };
+
+        this.mod_times = this.current_mod_times();
+        Ok(this)
+    }
+
+    /// Recompiles the shader if either source file has changed.
+    ///
+    /// Returns `Ok(true)` if a new program was swapped in, `Ok(false)` if
+    /// nothing changed, and an error if recompilation failed.
+    pub fn poll(&mut self) -> Result<bool> {
+        let mod_times = self.current_mod_times();
+
+        if mod_times == self.mod_times {
+            return Ok(false);
+        }
+
+        // Record the attempt even if it fails, so a broken file is only
+        // compiled once rather than on every poll.
+        self.mod_times = mod_times;
+        self.reload().map(|_| true)
+    }
+
+    /// Recompiles the shader unconditionally.
+    ///
+    /// If a source file is missing or fails to compile, [`OwnedShader::load`]
+    /// returns an error rather than raylib's default program, and the
+    /// previous program stays active.
+    pub fn reload(&mut self) -> Result<()> {
+        let (result, messages) =
+            log::capture(|| OwnedShader::load(self.vs_path.as_deref(), self.fs_path.as_deref()));
+
+        self.error_log = messages;
+
+        let shader = result?;
+
+        shader.resolve_like(&self.shader);
+        self.shader = shader;
+        self.generation += 1;
+
+        Ok(())
+    }
+
+    pub fn shader(&self) -> &OwnedShader {
+        &self.shader
+    }
+
+    /// Incremented every time a new program is swapped in, so callers holding
+    /// pre-resolved uniform locations know to resolve them again.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// The warnings and errors raylib logged during the last reload attempt,
+    /// including the GLSL compiler output if compilation failed.
+    pub fn error_log(&self) -> &[String] {
+        &self.error_log
+    }
+
+    pub fn vs_path(&self) -> Option<&Path> {
+        self.vs_path.as_deref()
+    }
+
+    pub fn fs_path(&self) -> Option<&Path> {
+        self.fs_path.as_deref()
+    }
+
+    fn current_mod_times(&self) -> [Option<SystemTime>; 2] {
+        // A file that briefly vanishes while an editor saves it reads as
+        // unchanged rather than as an error.
+        let mod_time = |path: &Option<PathBuf>, previous: Option<SystemTime>| match path {
+            Some(path) => crate::fs::file_mod_time(path).ok().or(previous),
+            None => None,
+        };
+
+        [
+            mod_time(&self.vs_path, self.mod_times[0]),
+            mod_time(&self.fs_path, self.mod_times[1]),
+        ]
+    }
+}
+
+impl Deref for ReloadableShader {
+    type Target = OwnedShader;
+
+    fn deref(&self) -> &Self::Target {
+        &self.shader
+    }
+}
@@ -0,0 +1,144 @@
+use crate::sys;
+use crate::sys::{Color, Matrix, ShaderUniformDataType, Vector2, Vector3, Vector4};
+use crate::texture::{OwnedRenderTexture, OwnedTexture};
+use std::os::raw::{c_int, c_void};
+
+/// A value that can be uploaded to a shader uniform.
+pub trait ShaderUniform {
+    /// Uploads the value to the uniform at `location`.
+    fn set_uniform(&self, shader: sys::Shader, location: i32);
+}
+
+/// A plain value with a GLSL counterpart, usable on its own or in arrays.
+///
+/// # Safety
+///
+/// `Self` must have exactly the memory layout raylib expects for
+/// `DATA_TYPE`, since values are passed to it as untyped pointers.
+pub unsafe trait UniformElement: Copy {
+    const DATA_TYPE: ShaderUniformDataType;
+}
+
+unsafe impl UniformElement for f32 {
+    const DATA_TYPE: ShaderUniformDataType = ShaderUniformDataType::SHADER_UNIFORM_FLOAT;
+}
+
+unsafe impl UniformElement for i32 {
+    const DATA_TYPE: ShaderUniformDataType = ShaderUniformDataType::SHADER_UNIFORM_INT;
+}
+
+unsafe impl UniformElement for Vector2 {
+    const DATA_TYPE: ShaderUniformDataType = ShaderUniformDataType::SHADER_UNIFORM_VEC2;
+}
+
+unsafe impl UniformElement for Vector3 {
+    const DATA_TYPE: ShaderUniformDataType = ShaderUniformDataType::SHADER_UNIFORM_VEC3;
+}
+
+unsafe impl UniformElement for Vector4 {
+    const DATA_TYPE: ShaderUniformDataType = ShaderUniformDataType::SHADER_UNIFORM_VEC4;
+}
+
+unsafe impl UniformElement for [f32; 2] {
+    const DATA_TYPE: ShaderUniformDataType = ShaderUniformDataType::SHADER_UNIFORM_VEC2;
+}
+
+unsafe impl UniformElement for [f32; 3] {
+    const DATA_TYPE: ShaderUniformDataType = ShaderUniformDataType::SHADER_UNIFORM_VEC3;
+}
+
+unsafe impl UniformElement for [f32; 4] {
+    const DATA_TYPE: ShaderUniformDataType = ShaderUniformDataType::SHADER_UNIFORM_VEC4;
+}
+
+unsafe impl UniformElement for [i32; 2] {
+    const DATA_TYPE: ShaderUniformDataType = ShaderUniformDataType::SHADER_UNIFORM_IVEC2;
+}
+
+unsafe impl UniformElement for [i32; 3] {
+    const DATA_TYPE: ShaderUniformDataType = ShaderUniformDataType::SHADER_UNIFORM_IVEC3;
+}
+
+unsafe impl UniformElement for [i32; 4] {
+    const DATA_TYPE: ShaderUniformDataType = ShaderUniformDataType::SHADER_UNIFORM_IVEC4;
+}
+
+fn set_elements<T: UniformElement>(shader: sys::Shader, location: i32, values: &[T]) {
+    unsafe {
+        sys::SetShaderValueV(
+            shader,
+            location,
+            values.as_ptr() as *const c_void,
+            T::DATA_TYPE as c_int,
+            values.len() as c_int,
+        )
+    }
+}
+
+macro_rules! impl_element_uniform {
+    ($($t:ty),*) => {
+        $(
+            impl ShaderUniform for $t {
+                fn set_uniform(&self, shader: sys::Shader, location: i32) {
+                    set_elements(shader, location, std::slice::from_ref(self));
+                }
+            }
+        )*
+    };
+}
+
+impl_element_uniform!(
+    f32, i32, Vector2, Vector3, Vector4, [f32; 2], [f32; 3], [f32; 4], [i32; 2], [i32; 3], [i32; 4]
+);
+
+impl<T: UniformElement> ShaderUniform for [T] {
+    fn set_uniform(&self, shader: sys::Shader, location: i32) {
+        set_elements(shader, location, self);
+    }
+}
+
+impl<T: UniformElement> ShaderUniform for Vec<T> {
+    fn set_uniform(&self, shader: sys::Shader, location: i32) {
+        set_elements(shader, location, self);
+    }
+}
+
+/// Colors are uploaded as a normalised `vec4`, matching `colDiffuse`.
+impl ShaderUniform for Color {
+    fn set_uniform(&self, shader: sys::Shader, location: i32) {
+        let normalized = [self.r, self.g, self.b, self.a].map(|c| c as f32 / 255.0);
+        normalized.set_uniform(shader, location);
+    }
+}
+
+impl ShaderUniform for Matrix {
+    fn set_uniform(&self, shader: sys::Shader, location: i32) {
+        unsafe { sys::SetShaderValueMatrix(shader, location, *self) }
+    }
+}
+
+/// Textures are bound to a sampler through `SetShaderValueTexture`.
+impl ShaderUniform for sys::Texture2D {
+    fn set_uniform(&self, shader: sys::Shader, location: i32) {
+        unsafe { sys::SetShaderValueTexture(shader, location, *self) }
+    }
+}
+
+impl ShaderUniform for OwnedTexture<'_> {
+    fn set_uniform(&self, shader: sys::Shader, location: i32) {
+        self.as_raw().set_uniform(shader, location);
+    }
+}
+
+/// Render textures bind their color attachment.
+impl ShaderUniform for OwnedRenderTexture<'_> {
+    fn set_uniform(&self, shader: sys::Shader, location: i32) {
+        self.texture().set_uniform(shader, location);
+    }
+}
+
+impl<T: ShaderUniform + ?Sized> ShaderUniform for &T {
+    fn set_uniform(&self, shader: sys::Shader, location: i32) {
+        (**self).set_uniform(shader, location);
+    }
+}
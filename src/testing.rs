@@ -0,0 +1,413 @@
+use crate::draw::TextureHandle;
+use crate::error::Result;
+use crate::image::OwnedImage;
+use crate::sys::{Color, ConfigFlags, PixelFormat};
+use crate::texture::OwnedRenderTexture;
+use crate::window::{Window, WindowBuilder};
+use crate::BLACK;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Set to any value to overwrite golden images with what is rendered,
+/// instead of comparing against them.
+pub const UPDATE_VAR: &str = "RRAYLIB_UPDATE_GOLDEN";
+
+/// Renders into off-screen targets and compares the results with golden
+/// images stored as PNGs.
+///
+/// The harness opens a hidden window for its OpenGL context. raylib supports
+/// one window per process, tied to the thread that opened it, so keep all
+/// golden tests in one `#[test]` or run them with `--test-threads=1`.
+///
+/// On CI machines without a GPU, run under Xvfb with Mesa's software
+/// renderer, or build with the `headless` feature to need no display at all:
+///
+/// ```text
+/// LIBGL_ALWAYS_SOFTWARE=1 xvfb-run -a cargo test
+/// cargo test --features headless
+/// ```
+///
+/// When an image doesn't match, `<name>.actual.png` and `<name>.diff.png`
+/// are written next to the golden image. The diff shows mismatched pixels
+/// in red over a faded copy of the golden image.
+///
+/// ```ignore
+/// let golden = Golden::new("tests/golden")?.with_tolerance(2);
+///
+/// golden.assert_matches("red_square", 64, 64, |canvas| {
+///     canvas.clear(BLACK);
+///     canvas.draw_rectangle(Rectangle { x: 16.0, y: 16.0, width: 32.0, height: 32.0 }, RED);
+/// });
+/// ```
+#[derive(Debug)]
+pub struct Golden {
+    window: Window,
+    dir: PathBuf,
+    tolerance: u8,
+    update: bool,
+}
+
+impl Golden {
+    /// Opens a hidden window and reads golden images from `dir`.
+    pub fn new(dir: impl Into<PathBuf>) -> Result<Self> {
+        let window = WindowBuilder::new(64, 64, "rraylib golden tests")
+            .flag(ConfigFlags::FLAG_WINDOW_HIDDEN)
+            .build()?;
+
+        Ok(Self {
+            window,
+            dir: dir.into(),
+            tolerance: 0,
+            update: std::env::var_os(UPDATE_VAR).is_some(),
+        })
+    }
+
+    /// Allows each channel of each pixel to differ by up to `tolerance`,
+    /// to absorb rounding differences between GPU drivers.
+    pub fn with_tolerance(mut self, tolerance: u8) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// The hidden window, for loading textures, fonts, and other GPU
+    /// resources to draw with.
+    pub fn window(&self) -> &Window {
+        &self.window
+    }
+
+    /// The directory golden images are read from.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Renders `draw` into a new `width` by `height` render texture and
+    /// reads the result back as an R8G8B8A8 image.
+    pub fn render(
+        &self,
+        width: i32,
+        height: i32,
+        draw: impl FnOnce(&mut TextureHandle<'_, '_>),
+    ) -> Result<OwnedImage> {
+        let mut target = OwnedRenderTexture::new(&self.window, width, height)?;
+        draw(&mut target.begin_drawing());
+
+        let mut image = target.capture()?;
+        image.convert(PixelFormat::PIXELFORMAT_UNCOMPRESSED_R8G8B8A8);
+        Ok(image)
+    }
+
+    /// Compares `actual` with the golden image `<name>.png`.
+    ///
+    /// If [`UPDATE_VAR`] is set, the golden image is overwritten instead and
+    /// the comparison always matches. If the golden image can't be loaded,
+    /// `actual` is written to `<name>.actual.png` before failing, so it can
+    /// be reviewed and renamed.
+    pub fn compare(&self, name: &str, actual: &OwnedImage) -> Result<Comparison> {
+        compare(&self.dir, name, actual, self.tolerance, self.update)
+    }
+
+    /// Renders `draw` and compares it with the golden image `<name>.png`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if rendering or loading fails, or if the images don't match.
+    #[track_caller]
+    pub fn assert_matches(
+        &self,
+        name: &str,
+        width: i32,
+        height: i32,
+        draw: impl FnOnce(&mut TextureHandle<'_, '_>),
+    ) {
+        let actual = self
+            .render(width, height, draw)
+            .unwrap_or_else(|e| panic!("failed to render '{name}': {e}"));
+
+        let comparison = self
+            .compare(name, &actual)
+            .unwrap_or_else(|e| panic!("failed to compare '{name}': {e}"));
+
+        assert!(comparison.is_match(), "'{name}' {comparison}");
+    }
+}
+
+/// The result of comparing a rendered image with a golden image.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Comparison {
+    /// The golden image's width and height.
+    pub expected_size: (i32, i32),
+    /// The rendered image's width and height.
+    pub actual_size: (i32, i32),
+    /// How many pixels differ by more than the tolerance in any channel.
+    pub mismatched: usize,
+    /// The largest difference in any channel of any pixel.
+    pub max_difference: u8,
+}
+
+impl Comparison {
+    fn identical(image: &OwnedImage) -> Self {
+        let size = (image.width(), image.height());
+
+        Self {
+            expected_size: size,
+            actual_size: size,
+            mismatched: 0,
+            max_difference: 0,
+        }
+    }
+
+    /// Whether the images are the same size and every pixel is within
+    /// tolerance.
+    pub fn is_match(&self) -> bool {
+        self.expected_size == self.actual_size && self.mismatched == 0
+    }
+}
+
+impl fmt::Display for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (ew, eh) = self.expected_size;
+        let (aw, ah) = self.actual_size;
+
+        if self.expected_size != self.actual_size {
+            write!(f, "is {aw}x{ah}, expected {ew}x{eh}")
+        } else if self.mismatched == 0 {
+            write!(f, "matches")
+        } else {
+            write!(
+                f,
+                "has {} of {} pixels mismatched, differing by up to {}",
+                self.mismatched,
+                ew as usize * eh as usize,
+                self.max_difference
+            )
+        }
+    }
+}
+
+/// Compares `actual` with `<dir>/<name>.png`, or overwrites it if `update`
+/// is set. Only the images' files are touched, so this needs no window.
+fn compare(
+    dir: &Path,
+    name: &str,
+    actual: &OwnedImage,
+    tolerance: u8,
+    update: bool,
+) -> Result<Comparison> {
+    let path = |extension: &str| dir.join(format!("{name}.{extension}"));
+    let actual = actual.converted(PixelFormat::PIXELFORMAT_UNCOMPRESSED_R8G8B8A8);
+    let golden_path = path("png");
+
+    if update {
+        if let Some(parent) = golden_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        actual.export(&golden_path)?;
+        return Ok(Comparison::identical(&actual));
+    }
+
+    let expected = match OwnedImage::load(&golden_path) {
+        Ok(expected) => expected.converted(PixelFormat::PIXELFORMAT_UNCOMPRESSED_R8G8B8A8),
+        Err(e) => {
+            actual.export(path("actual.png"))?;
+            return Err(e);
+        }
+    };
+
+    let (comparison, diff) = diff(&expected, &actual, tolerance);
+
+    if !comparison.is_match() {
+        actual.export(path("actual.png"))?;
+
+        if let Some(diff) = diff {
+            diff.export(path("diff.png"))?;
+        }
+    }
+
+    Ok(comparison)
+}
+
+/// Compares two R8G8B8A8 images, returning a diff image if they are the same
+/// size.
+fn diff(
+    expected: &OwnedImage,
+    actual: &OwnedImage,
+    tolerance: u8,
+) -> (Comparison, Option<OwnedImage>) {
+    let mut comparison = Comparison {
+        expected_size: (expected.width(), expected.height()),
+        actual_size: (actual.width(), actual.height()),
+        mismatched: 0,
+        max_difference: 0,
+    };
+
+    let (Some(expected_pixels), Some(actual_pixels)) =
+        (expected.pixels::<Color>(), actual.pixels::<Color>())
+    else {
+        return (comparison, None);
+    };
+
+    if comparison.expected_size != comparison.actual_size {
+        return (comparison, None);
+    }
+
+    let faded = |c: u8| 64 + c / 4;
+    let mut diff = OwnedImage::new(expected.width(), expected.height(), BLACK);
+
+    let diff_pixels = diff
+        .pixels_mut::<Color>()
+        .expect("generated images are R8G8B8A8");
+
+    for ((e, a), d) in expected_pixels.iter().zip(actual_pixels).zip(diff_pixels) {
+        let difference = [
+            e.r.abs_diff(a.r),
+            e.g.abs_diff(a.g),
+            e.b.abs_diff(a.b),
+            e.a.abs_diff(a.a),
+        ]
+        .into_iter()
+        .max()
+        .unwrap_or(0);
+
+        comparison.max_difference = comparison.max_difference.max(difference);
+
+        *d = if difference > tolerance {
+            comparison.mismatched += 1;
+            Color {
+                r: 255,
+                g: 0,
+                b: 0,
+                a: 255,
+            }
+        } else {
+            let gray = faded(((e.r as u16 + e.g as u16 + e.b as u16) / 3) as u8);
+            Color {
+                r: gray,
+                g: gray,
+                b: gray,
+                a: 255,
+            }
+        };
+    }
+
+    (comparison, Some(diff))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{RED, WHITE};
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("rraylib-golden-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn with_pixel(mut image: OwnedImage, x: i32, y: i32, color: Color) -> OwnedImage {
+        image.set_pixel(x, y, color);
+        image
+    }
+
+    fn diff_matches(expected: &OwnedImage, actual: &OwnedImage, tolerance: u8) -> bool {
+        diff(expected, actual, tolerance).0.is_match()
+    }
+
+    #[test]
+    fn identical_images_match() {
+        let image = OwnedImage::new(4, 3, WHITE);
+        let (comparison, diff) = diff(&image, &image.clone(), 0);
+
+        assert!(comparison.is_match());
+        assert_eq!(comparison, Comparison::identical(&image));
+        assert_eq!(comparison.to_string(), "matches");
+
+        let diff = diff.unwrap();
+        assert_eq!((diff.width(), diff.height()), (4, 3));
+        assert!(diff.pixels::<Color>().unwrap().iter().all(|&c| c != RED));
+    }
+
+    #[test]
+    fn pixels_over_tolerance_are_counted() {
+        let expected = OwnedImage::new(4, 3, Color::new(100, 100, 100, 255));
+        let actual = with_pixel(expected.clone(), 1, 0, Color::new(102, 100, 100, 255));
+        let actual = with_pixel(actual, 2, 2, Color::new(100, 100, 100, 252));
+
+        let (comparison, diff) = diff(&expected, &actual, 2);
+
+        assert!(!comparison.is_match());
+        assert_eq!(comparison.mismatched, 1);
+        assert_eq!(comparison.max_difference, 3);
+        assert_eq!(
+            comparison.to_string(),
+            "has 1 of 12 pixels mismatched, differing by up to 3"
+        );
+
+        let diff = diff.unwrap();
+        let red = Color::new(255, 0, 0, 255);
+        assert_eq!(diff.get_pixel(2, 2), Some(red));
+        assert_ne!(diff.get_pixel(1, 0), Some(red));
+
+        assert!(diff_matches(&expected, &actual, 3));
+    }
+
+    #[test]
+    fn different_sizes_never_match() {
+        let (comparison, diff) = diff(
+            &OwnedImage::new(4, 3, WHITE),
+            &OwnedImage::new(3, 4, WHITE),
+            255,
+        );
+
+        assert!(!comparison.is_match());
+        assert!(diff.is_none());
+        assert_eq!(comparison.to_string(), "is 3x4, expected 4x3");
+    }
+
+    #[test]
+    fn update_mode_writes_the_golden_image() {
+        let dir = temp_dir("update");
+        let image = OwnedImage::new(4, 3, RED);
+
+        let comparison = compare(&dir, "nested/red", &image, 0, true).unwrap();
+        assert!(comparison.is_match());
+        assert!(dir.join("nested/red.png").is_file());
+
+        let comparison = compare(&dir, "nested/red", &image, 0, false).unwrap();
+        assert!(comparison.is_match());
+        assert!(!dir.join("nested/red.actual.png").exists());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn missing_golden_images_write_the_actual_image() {
+        let dir = temp_dir("missing");
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let result = compare(&dir, "missing", &OwnedImage::new(2, 2, RED), 0, false);
+
+        assert!(result.is_err());
+        assert!(dir.join("missing.actual.png").is_file());
+        assert!(!dir.join("missing.png").exists());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn mismatches_write_the_actual_and_diff_images() {
+        let dir = temp_dir("mismatch");
+        let expected = OwnedImage::new(2, 2, WHITE);
+        compare(&dir, "square", &expected, 0, true).unwrap();
+
+        let actual = with_pixel(expected.clone(), 0, 1, RED);
+        let comparison = compare(&dir, "square", &actual, 0, false).unwrap();
+
+        assert_eq!(comparison.mismatched, 1);
+        assert!(dir.join("square.actual.png").is_file());
+        assert!(dir.join("square.diff.png").is_file());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,309 @@
+use crate::draw::TextureHandle;
+use crate::error::{self, ResourceKind, Result};
+use crate::image::OwnedImage;
+use crate::sys;
+use crate::sys::{Rectangle, TextureFilter, TextureWrap};
+use crate::window::Window;
+use std::marker::PhantomData;
+use std::mem::ManuallyDrop;
+use std::os::raw::{c_int, c_void};
+use std::path::Path;
+
+mod animated;
+
+pub use animated::*;
+
+/// A GPU texture that is unloaded when dropped.
+///
+/// The texture borrows the [`Window`] whose OpenGL context it lives in, so it
+/// cannot outlive it.
+#[derive(Debug)]
+pub struct OwnedTexture<'w> {
+    raw: sys::Texture2D,
+    _window: PhantomData<&'w Window>,
+}
+
+impl<'w> OwnedTexture<'w> {
+    /// Loads a texture from a file.
+    pub fn load(_window: &'w Window, path: impl AsRef<Path>) -> Result<Self> {
+        error::load(
+            ResourceKind::Texture,
+            path.as_ref(),
+            |path| unsafe { Self::from_raw(sys::LoadTexture(path.as_ptr())) },
+            |texture| unsafe { sys::IsTextureValid(texture.raw) },
+        )
+    }
+
+    /// Uploads an image to a new texture.
+    pub fn from_image(_window: &'w Window, image: &OwnedImage) -> Result<Self> {
+        error::load(
+            ResourceKind::Texture,
+            Path::new("<image>"),
+            |_| unsafe { Self::from_raw(sys::LoadTextureFromImage(*image.as_raw())) },
+            |texture| unsafe { sys::IsTextureValid(texture.raw) },
+        )
+    }
+
+    /// Takes ownership of a raw texture.
+    ///
+    /// # Safety
+    ///
+    /// `raw` must be a valid texture that is not unloaded anywhere else, and
+    /// the returned value must not outlive the window it was created with.
+    pub unsafe fn from_raw(raw: sys::Texture2D) -> Self {
+        Self {
+            raw,
+            _window: PhantomData,
+        }
+    }
+
+    /// Releases ownership of the texture without unloading it.
+    pub fn into_raw(self) -> sys::Texture2D {
+        ManuallyDrop::new(self).raw
+    }
+
+    pub fn as_raw(&self) -> &sys::Texture2D {
+        &self.raw
+    }
+
+    pub fn width(&self) -> i32 {
+        self.raw.width
+    }
+
+    pub fn height(&self) -> i32 {
+        self.raw.height
+    }
+
+    pub fn mipmaps(&self) -> i32 {
+        self.raw.mipmaps
+    }
+
+    /// Replaces the texture's pixels.
+    ///
+    /// `pixels` must be in the texture's pixel format.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `pixels` is not exactly the size of the texture's data.
+    pub fn update(&mut self, pixels: &[u8]) {
+        update(&self.raw, pixels);
+    }
+
+    /// Replaces the pixels inside `rect`.
+    ///
+    /// `pixels` must be in the texture's pixel format.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rect` is not inside the texture, or if `pixels` is not
+    /// exactly the size of the rectangle's data.
+    pub fn update_rect(&mut self, rect: Rectangle, pixels: &[u8]) {
+        update_rect(&self.raw, rect, pixels);
+    }
+
+    pub fn set_filter(&mut self, filter: TextureFilter) {
+        set_filter(&self.raw, filter);
+    }
+
+    pub fn set_wrap(&mut self, wrap: TextureWrap) {
+        set_wrap(&self.raw, wrap);
+    }
+
+    /// Generates a full mipmap chain for the texture.
+    pub fn gen_mipmaps(&mut self) {
+        unsafe { sys::GenTextureMipmaps(&mut self.raw) }
+    }
+
+    /// Downloads the texture into a CPU-side image.
+    pub fn to_image(&self) -> Result<OwnedImage> {
+        to_image(&self.raw)
+    }
+}
+
+impl Drop for OwnedTexture<'_> {
+    fn drop(&mut self) {
+        if self.raw.id != 0 {
+            unsafe { sys::UnloadTexture(self.raw) }
+        }
+    }
+}
+
+/// An off-screen framebuffer with a color texture, unloaded when dropped.
+///
+/// Like [`OwnedTexture`], it borrows the [`Window`] it was created with.
+#[derive(Debug)]
+pub struct OwnedRenderTexture<'w> {
+    raw: sys::RenderTexture2D,
+    _window: PhantomData<&'w Window>,
+}
+
+impl<'w> OwnedRenderTexture<'w> {
+    /// Creates a render texture with an RGBA color attachment and a depth
+    /// buffer.
+    pub fn new(_window: &'w Window, width: i32, height: i32) -> Result<Self> {
+        error::load(
+            ResourceKind::RenderTexture,
+            Path::new("<memory>"),
+            |_| unsafe { Self::from_raw(sys::LoadRenderTexture(width, height)) },
+            |target| unsafe { sys::IsRenderTextureValid(target.raw) },
+        )
+    }
+
+    /// Takes ownership of a raw render texture.
+    ///
+    /// # Safety
+    ///
+    /// `raw` must be a valid render texture that is not unloaded anywhere
+    /// else, and the returned value must not outlive the window it was
+    /// created with.
+    pub unsafe fn from_raw(raw: sys::RenderTexture2D) -> Self {
+        Self {
+            raw,
+            _window: PhantomData,
+        }
+    }
+
+    /// Releases ownership of the render texture without unloading it.
+    pub fn into_raw(self) -> sys::RenderTexture2D {
+        ManuallyDrop::new(self).raw
+    }
+
+    pub fn as_raw(&self) -> &sys::RenderTexture2D {
+        &self.raw
+    }
+
+    /// Begins drawing into the render texture. Drawing ends when the
+    /// returned handle is dropped.
+    pub fn begin_drawing(&mut self) -> TextureHandle<'_, 'w> {
+        TextureHandle::new(self)
+    }
+
+    /// The color attachment, for drawing the render texture or binding it to
+    /// a shader.
+    pub fn texture(&self) -> &sys::Texture2D {
+        &self.raw.texture
+    }
+
+    pub fn width(&self) -> i32 {
+        self.raw.texture.width
+    }
+
+    pub fn height(&self) -> i32 {
+        self.raw.texture.height
+    }
+
+    /// Replaces the color attachment's pixels.
+    ///
+    /// `pixels` must be in the attachment's pixel format, bottom row first
+    /// like the rest of the framebuffer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `pixels` is not exactly the size of the attachment's data.
+    pub fn update(&mut self, pixels: &[u8]) {
+        update(&self.raw.texture, pixels);
+    }
+
+    /// Replaces the color attachment's pixels inside `rect`, which is
+    /// measured from the bottom-left corner.
+    ///
+    /// `pixels` must be in the attachment's pixel format, bottom row first.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rect` is not inside the attachment, or if `pixels` is not
+    /// exactly the size of the rectangle's data.
+    pub fn update_rect(&mut self, rect: Rectangle, pixels: &[u8]) {
+        update_rect(&self.raw.texture, rect, pixels);
+    }
+
+    pub fn set_filter(&mut self, filter: TextureFilter) {
+        set_filter(&self.raw.texture, filter);
+    }
+
+    pub fn set_wrap(&mut self, wrap: TextureWrap) {
+        set_wrap(&self.raw.texture, wrap);
+    }
+
+    /// Generates a full mipmap chain for the color attachment.
+    pub fn gen_mipmaps(&mut self) {
+        unsafe { sys::GenTextureMipmaps(&mut self.raw.texture) }
+    }
+
+    /// Downloads the color attachment into a CPU-side image.
+    ///
+    /// The image is upside down, as OpenGL stores framebuffers bottom row
+    /// first.
+    pub fn to_image(&self) -> Result<OwnedImage> {
+        to_image(&self.raw.texture)
+    }
+
+    /// Downloads the color attachment into a CPU-side image, flipped the
+    /// right way up.
+    pub fn capture(&self) -> Result<OwnedImage> {
+        let mut image = self.to_image()?;
+        image.flip_vertical();
+        Ok(image)
+    }
+}
+
+impl Drop for OwnedRenderTexture<'_> {
+    fn drop(&mut self) {
+        if self.raw.id != 0 {
+            unsafe { sys::UnloadRenderTexture(self.raw) }
+        }
+    }
+}
+
+fn update(texture: &sys::Texture2D, pixels: &[u8]) {
+    check_pixels(texture, texture.width, texture.height, pixels);
+    unsafe { sys::UpdateTexture(*texture, pixels.as_ptr() as *const c_void) }
+}
+
+fn update_rect(texture: &sys::Texture2D, rect: Rectangle, pixels: &[u8]) {
+    check_rect(texture, rect);
+    check_pixels(texture, rect.width as i32, rect.height as i32, pixels);
+    unsafe { sys::UpdateTextureRec(*texture, rect, pixels.as_ptr() as *const c_void) }
+}
+
+fn set_filter(texture: &sys::Texture2D, filter: TextureFilter) {
+    unsafe { sys::SetTextureFilter(*texture, filter as c_int) }
+}
+
+fn set_wrap(texture: &sys::Texture2D, wrap: TextureWrap) {
+    unsafe { sys::SetTextureWrap(*texture, wrap as c_int) }
+}
+
+fn to_image(texture: &sys::Texture2D) -> Result<OwnedImage> {
+    error::load(
+        ResourceKind::Image,
+        Path::new("<texture>"),
+        |_| unsafe { OwnedImage::from_raw(sys::LoadImageFromTexture(*texture)) },
+        |image| unsafe { sys::IsImageValid(*image.as_raw()) },
+    )
+}
+
+fn check_pixels(texture: &sys::Texture2D, width: i32, height: i32, pixels: &[u8]) {
+    let expected = unsafe { sys::GetPixelDataSize(width, height, texture.format) } as usize;
+
+    assert_eq!(
+        pixels.len(),
+        expected,
+        "pixel data is {} bytes, expected {expected} for {width}x{height}",
+        pixels.len()
+    );
+}
+
+fn check_rect(texture: &sys::Texture2D, rect: Rectangle) {
+    assert!(
+        rect.x >= 0.0
+            && rect.y >= 0.0
+            && rect.width >= 0.0
+            && rect.height >= 0.0
+            && rect.x + rect.width <= texture.width as f32
+            && rect.y + rect.height <= texture.height as f32,
+        "rectangle {rect:?} is outside the {}x{} texture",
+        texture.width,
+        texture.height
+    );
+}
@@ -0,0 +1,116 @@
+use super::OwnedTexture;
+use crate::error::Result;
+use crate::image::AnimatedImage;
+use crate::window::Window;
+use std::path::Path;
+use std::time::Duration;
+
+/// A texture that plays an [`AnimatedImage`], uploading each frame as it
+/// comes due.
+///
+/// Call [`Self::update`] once per frame with the frame time.
+#[derive(Debug)]
+pub struct AnimatedTexture<'w> {
+    texture: OwnedTexture<'w>,
+    animation: AnimatedImage,
+    frame: usize,
+    elapsed: Duration,
+    playing: bool,
+}
+
+impl<'w> AnimatedTexture<'w> {
+    /// Uploads the first frame of `animation` to a new texture.
+    pub fn new(window: &'w Window, animation: AnimatedImage) -> Result<Self> {
+        let first = animation
+            .frame_image(0)
+            .expect("animations have at least one frame");
+        let texture = OwnedTexture::from_image(window, &first)?;
+
+        Ok(Self {
+            texture,
+            animation,
+            frame: 0,
+            elapsed: Duration::ZERO,
+            playing: true,
+        })
+    }
+
+    /// Loads an animation from a file and uploads its first frame.
+    pub fn load(window: &'w Window, path: impl AsRef<Path>) -> Result<Self> {
+        Self::new(window, AnimatedImage::load(path)?)
+    }
+
+    /// Advances the animation by `dt` seconds, uploading a new frame if the
+    /// current one has changed. Does nothing while paused.
+    pub fn update(&mut self, dt: f32) {
+        if !self.playing {
+            return;
+        }
+
+        // Negative, NaN, and infinite steps don't advance the clock.
+        let dt = Duration::try_from_secs_f32(dt).unwrap_or_default();
+        self.elapsed = self.elapsed.saturating_add(dt);
+
+        // Keep the clock within one loop so it never overflows.
+        let duration = self.animation.duration();
+
+        if !duration.is_zero() && self.elapsed >= duration {
+            self.elapsed =
+                Duration::from_nanos((self.elapsed.as_nanos() % duration.as_nanos()) as u64);
+        }
+
+        self.show(self.animation.frame_at(self.elapsed));
+    }
+
+    /// Jumps to frame `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of range.
+    pub fn set_frame(&mut self, index: usize) {
+        assert!(
+            index < self.animation.frame_count(),
+            "frame {index} out of range for an animation of {} frames",
+            self.animation.frame_count()
+        );
+
+        self.elapsed = self.animation.delays()[..index].iter().sum();
+        self.show(index);
+    }
+
+    pub fn play(&mut self) {
+        self.playing = true;
+    }
+
+    pub fn pause(&mut self) {
+        self.playing = false;
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.playing
+    }
+
+    pub fn frame(&self) -> usize {
+        self.frame
+    }
+
+    /// The texture holding the current frame.
+    pub fn texture(&self) -> &OwnedTexture<'w> {
+        &self.texture
+    }
+
+    pub fn animation(&self) -> &AnimatedImage {
+        &self.animation
+    }
+
+    fn show(&mut self, index: usize) {
+        if index == self.frame {
+            return;
+        }
+
+        if let Some(bytes) = self.animation.frame_bytes(index) {
+            self.texture.update(bytes);
+            self.frame = index;
+        }
+    }
+}
@@ -0,0 +1,409 @@
+//! Operators and raymath-style methods for [`Vector2`], [`Vector3`], and
+//! [`Vector4`], written in Rust so they inline and need no `unsafe`.
+
+use crate::sys::{Vector2, Vector3, Vector4};
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+/// Implements everything shared by the vector types, component by component.
+macro_rules! vector {
+    ($ty:ident { $($field:ident),+ }) => {
+        impl $ty {
+            pub const ZERO: Self = Self::splat(0.0);
+            pub const ONE: Self = Self::splat(1.0);
+
+            pub const fn new($($field: f32),+) -> Self {
+                Self { $($field),+ }
+            }
+
+            /// A vector with every component set to `value`.
+            pub const fn splat(value: f32) -> Self {
+                Self { $($field: value),+ }
+            }
+
+            pub const fn dot(self, other: Self) -> f32 {
+                0.0 $(+ self.$field * other.$field)+
+            }
+
+            /// The squared length, which is cheaper than [`Self::length`].
+            pub const fn length_sqr(self) -> f32 {
+                self.dot(self)
+            }
+
+            pub fn length(self) -> f32 {
+                self.length_sqr().sqrt()
+            }
+
+            pub fn distance(self, other: Self) -> f32 {
+                (other - self).length()
+            }
+
+            pub const fn distance_sqr(self, other: Self) -> f32 {
+                Self { $($field: other.$field - self.$field),+ }.length_sqr()
+            }
+
+            /// Scales the vector to a length of 1. A zero vector is returned
+            /// unchanged.
+            pub fn normalize(self) -> Self {
+                let length = self.length();
+
+                if length > 0.0 {
+                    self / length
+                } else {
+                    self
+                }
+            }
+
+            /// Interpolates towards `other` by `amount`, which is not clamped.
+            pub const fn lerp(self, other: Self, amount: f32) -> Self {
+                Self { $($field: self.$field + amount * (other.$field - self.$field)),+ }
+            }
+
+            /// Moves towards `target` by at most `max_distance`, stopping on
+            /// it.
+            pub fn move_towards(self, target: Self, max_distance: f32) -> Self {
+                let delta = target - self;
+                let distance_sqr = delta.length_sqr();
+
+                if distance_sqr == 0.0
+                    || (max_distance >= 0.0 && distance_sqr <= max_distance * max_distance)
+                {
+                    return target;
+                }
+
+                self + delta * (max_distance / distance_sqr.sqrt())
+            }
+
+            /// Clamps each component between `min`'s and `max`'s.
+            pub fn clamp(self, min: Self, max: Self) -> Self {
+                Self { $($field: self.$field.max(min.$field).min(max.$field)),+ }
+            }
+
+            /// Clamps the vector's length between `min` and `max`, keeping its
+            /// direction. Unlike [`f32::clamp`], this doesn't panic when `min`
+            /// is greater than `max`, matching `Vector2ClampValue`.
+            pub fn clamp_length(self, min: f32, max: f32) -> Self {
+                let length_sqr = self.length_sqr();
+
+                if length_sqr <= 0.0 {
+                    return self;
+                }
+
+                let length = length_sqr.sqrt();
+
+                if length < min {
+                    self * (min / length)
+                } else if length > max {
+                    self * (max / length)
+                } else {
+                    self
+                }
+            }
+
+            /// The smaller of each component.
+            pub fn min(self, other: Self) -> Self {
+                Self { $($field: self.$field.min(other.$field)),+ }
+            }
+
+            /// The larger of each component.
+            pub fn max(self, other: Self) -> Self {
+                Self { $($field: self.$field.max(other.$field)),+ }
+            }
+
+            /// Reflects the vector off a surface with the given unit `normal`.
+            pub fn reflect(self, normal: Self) -> Self {
+                self - normal * (2.0 * self.dot(normal))
+            }
+
+            /// Refracts the vector through a surface with the given unit
+            /// `normal`, where `ratio` is the ratio of the refractive indices.
+            /// Returns zero on total internal reflection.
+            ///
+            /// The vector should be normalized.
+            pub fn refract(self, normal: Self, ratio: f32) -> Self {
+                let dot = self.dot(normal);
+                let d = 1.0 - ratio * ratio * (1.0 - dot * dot);
+
+                if d < 0.0 {
+                    return Self::ZERO;
+                }
+
+                self * ratio - normal * (ratio * dot + d.sqrt())
+            }
+        }
+
+        impl Default for $ty {
+            fn default() -> Self {
+                Self::ZERO
+            }
+        }
+
+        impl PartialEq for $ty {
+            fn eq(&self, other: &Self) -> bool {
+                true $(&& self.$field == other.$field)+
+            }
+        }
+
+        impl Neg for $ty {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                Self { $($field: -self.$field),+ }
+            }
+        }
+
+        impl Sum for $ty {
+            fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+                iter.fold(Self::ZERO, Add::add)
+            }
+        }
+
+        vector!(@op $ty { $($field),+ } Add add AddAssign add_assign +);
+        vector!(@op $ty { $($field),+ } Sub sub SubAssign sub_assign -);
+        vector!(@op $ty { $($field),+ } Mul mul MulAssign mul_assign *);
+        vector!(@op $ty { $($field),+ } Div div DivAssign div_assign /);
+
+        impl Mul<$ty> for f32 {
+            type Output = $ty;
+
+            fn mul(self, rhs: $ty) -> $ty {
+                rhs * self
+            }
+        }
+    };
+
+    // A component-wise operator, with a scalar on the right, and their
+    // assigning forms.
+    (@op $ty:ident { $($field:ident),+ } $op:ident $method:ident $assign:ident $assign_method:ident $sym:tt) => {
+        impl $op for $ty {
+            type Output = Self;
+
+            fn $method(self, rhs: Self) -> Self {
+                Self { $($field: self.$field $sym rhs.$field),+ }
+            }
+        }
+
+        impl $op<f32> for $ty {
+            type Output = Self;
+
+            fn $method(self, rhs: f32) -> Self {
+                Self { $($field: self.$field $sym rhs),+ }
+            }
+        }
+
+        impl $assign for $ty {
+            fn $assign_method(&mut self, rhs: Self) {
+                *self = *self $sym rhs;
+            }
+        }
+
+        impl $assign<f32> for $ty {
+            fn $assign_method(&mut self, rhs: f32) {
+                *self = *self $sym rhs;
+            }
+        }
+    };
+}
+
+vector!(Vector2 { x, y });
+vector!(Vector3 { x, y, z });
+vector!(Vector4 { x, y, z, w });
+
+impl Vector2 {
+    /// The z component of the 3D cross product, which is positive when
+    /// `other` is counter-clockwise from the vector.
+    pub const fn cross(self, other: Self) -> f32 {
+        self.x * other.y - self.y * other.x
+    }
+
+    /// The signed angle in radians from the vector to `other`.
+    pub fn angle(self, other: Self) -> f32 {
+        self.cross(other).atan2(self.dot(other))
+    }
+
+    /// Rotates the vector by `angle` radians.
+    pub fn rotate(self, angle: f32) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        Self::new(self.x * cos - self.y * sin, self.x * sin + self.y * cos)
+    }
+}
+
+impl Vector3 {
+    pub const fn cross(self, other: Self) -> Self {
+        Self::new(
+            self.y * other.z - self.z * other.y,
+            self.z * other.x - self.x * other.z,
+            self.x * other.y - self.y * other.x,
+        )
+    }
+
+    /// The unsigned angle in radians between the vector and `other`.
+    pub fn angle(self, other: Self) -> f32 {
+        self.cross(other).length().atan2(self.dot(other))
+    }
+
+    /// Rotates the vector by `angle` radians around `axis`, like
+    /// `Vector3RotateByAxisAngle`.
+    pub fn rotate(self, axis: Self, angle: f32) -> Self {
+        let (sin, cos) = (angle / 2.0).sin_cos();
+        let w = axis.normalize() * sin;
+        let wv = w.cross(self);
+        let wwv = w.cross(wv);
+
+        self + wv * (2.0 * cos) + wwv * 2.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sys;
+
+    const EPSILON: f32 = 1e-5;
+
+    fn vector2s() -> impl Iterator<Item = Vector2> + Clone {
+        [
+            Vector2::ZERO,
+            Vector2::new(1.0, 0.0),
+            Vector2::new(0.0, -1.0),
+            Vector2::new(3.0, 4.0),
+            Vector2::new(-2.5, 0.5),
+            Vector2::new(-7.0, -1.25),
+            Vector2::new(1e-3, 2e-3),
+        ]
+        .into_iter()
+    }
+
+    fn vector3s() -> impl Iterator<Item = Vector3> + Clone {
+        [
+            Vector3::ZERO,
+            Vector3::new(1.0, 0.0, 0.0),
+            Vector3::new(0.0, 1.0, 0.0),
+            Vector3::new(0.0, 0.0, -1.0),
+            Vector3::new(1.0, 2.0, 3.0),
+            Vector3::new(-4.0, 0.5, 2.0),
+            Vector3::new(0.25, -3.0, -1.5),
+        ]
+        .into_iter()
+    }
+
+    fn assert_close2(actual: Vector2, expected: Vector2) {
+        assert!(
+            (actual - expected).length() <= EPSILON * expected.length().max(1.0),
+            "{actual:?} != {expected:?}"
+        );
+    }
+
+    fn assert_close3(actual: Vector3, expected: Vector3) {
+        assert!(
+            (actual - expected).length() <= EPSILON * expected.length().max(1.0),
+            "{actual:?} != {expected:?}"
+        );
+    }
+
+    fn assert_close(actual: f32, expected: f32) {
+        assert!(
+            (actual - expected).abs() <= EPSILON * expected.abs().max(1.0),
+            "{actual} != {expected}"
+        );
+    }
+
+    #[test]
+    fn angle_matches_raymath() {
+        for (a, b) in vector2s().flat_map(|a| vector2s().map(move |b| (a, b))) {
+            assert_close(a.angle(b), unsafe { sys::Vector2Angle(a, b) });
+        }
+
+        for (a, b) in vector3s().flat_map(|a| vector3s().map(move |b| (a, b))) {
+            assert_close(a.angle(b), unsafe { sys::Vector3Angle(a, b) });
+        }
+    }
+
+    #[test]
+    fn rotate_matches_raymath() {
+        const ANGLES: [f32; 6] = [-3.5, -1.0, 0.0, 0.5, 1.5707964, 6.0];
+
+        for v in vector2s() {
+            for angle in ANGLES {
+                assert_close2(v.rotate(angle), unsafe { sys::Vector2Rotate(v, angle) });
+            }
+        }
+
+        for (v, axis) in vector3s().flat_map(|v| vector3s().skip(1).map(move |a| (v, a))) {
+            for angle in ANGLES {
+                assert_close3(v.rotate(axis, angle), unsafe {
+                    sys::Vector3RotateByAxisAngle(v, axis, angle)
+                });
+            }
+        }
+    }
+
+    #[test]
+    fn move_towards_matches_raymath() {
+        const DISTANCES: [f32; 6] = [-2.0, 0.0, 0.5, 1.0, 5.0, 100.0];
+
+        for (v, target) in vector2s().flat_map(|v| vector2s().map(move |t| (v, t))) {
+            for distance in DISTANCES {
+                assert_close2(v.move_towards(target, distance), unsafe {
+                    sys::Vector2MoveTowards(v, target, distance)
+                });
+            }
+        }
+    }
+
+    #[test]
+    fn refract_matches_raymath() {
+        const RATIOS: [f32; 5] = [0.5, 1.0 / 1.33, 1.0, 1.33, 2.0];
+
+        for v in vector3s().skip(1).map(Vector3::normalize) {
+            for normal in vector3s().skip(1).map(Vector3::normalize) {
+                for ratio in RATIOS {
+                    assert_close3(v.refract(normal, ratio), unsafe {
+                        sys::Vector3Refract(v, normal, ratio)
+                    });
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn clamp_length_matches_raymath() {
+        const BOUNDS: [(f32, f32); 5] =
+            [(0.0, 1.0), (2.0, 3.0), (1.0, 1.0), (0.0, 100.0), (3.0, 2.0)];
+
+        for v in vector2s() {
+            for (min, max) in BOUNDS {
+                assert_close2(v.clamp_length(min, max), unsafe {
+                    sys::Vector2ClampValue(v, min, max)
+                });
+            }
+        }
+
+        for v in vector3s() {
+            for (min, max) in BOUNDS {
+                assert_close3(v.clamp_length(min, max), unsafe {
+                    sys::Vector3ClampValue(v, min, max)
+                });
+            }
+        }
+    }
+
+    #[test]
+    fn clamp_length_scales_to_the_nearest_bound() {
+        let v = Vector2::new(3.0, 4.0);
+
+        assert_close2(v.clamp_length(0.0, 1.0), Vector2::new(0.6, 0.8));
+        assert_close2(v.clamp_length(10.0, 20.0), Vector2::new(6.0, 8.0));
+        assert_eq!(v.clamp_length(1.0, 10.0), v);
+        assert_eq!(Vector3::ZERO.clamp_length(1.0, 2.0), Vector3::ZERO);
+    }
+
+    #[test]
+    fn clamp_length_accepts_inverted_bounds() {
+        let v = Vector2::new(3.0, 4.0);
+
+        assert_close2(v.clamp_length(10.0, 1.0), Vector2::new(6.0, 8.0));
+        assert_close2(v.clamp_length(2.0, 1.0), Vector2::new(0.6, 0.8));
+        assert!(v.clamp_length(f32::NAN, f32::NAN).x.is_finite());
+    }
+}
@@ -0,0 +1,171 @@
+use crate::draw::DrawHandle;
+use crate::error::{Error, Result};
+use crate::log;
+use crate::sys;
+use crate::sys::ConfigFlags;
+use std::ffi::CString;
+use std::io;
+use std::marker::PhantomData;
+use std::os::raw::c_int;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Whether a [`Window`] currently exists; raylib only supports one.
+static OPEN: AtomicBool = AtomicBool::new(false);
+
+/// `GLFW_PLATFORM`, the init hint selecting GLFW's platform backend.
+#[cfg(feature = "headless")]
+const GLFW_PLATFORM: c_int = 0x0005_0003;
+
+/// `GLFW_PLATFORM_NULL`, GLFW's platform with no display.
+#[cfg(feature = "headless")]
+const GLFW_PLATFORM_NULL: c_int = 0x0006_0005;
+
+// raylib statically links its bundled GLFW, so its functions can be called
+// directly.
+#[cfg(feature = "headless")]
+unsafe extern "C" {
+    fn glfwInitHint(hint: c_int, value: c_int);
+}
+
+/// Configures a [`Window`] before it is opened.
+#[derive(Debug, Clone)]
+pub struct WindowBuilder {
+    width: i32,
+    height: i32,
+    title: String,
+    flags: u32,
+    target_fps: Option<u32>,
+}
+
+impl WindowBuilder {
+    pub fn new(width: i32, height: i32, title: impl Into<String>) -> Self {
+        Self {
+            width,
+            height,
+            title: title.into(),
+            flags: 0,
+            target_fps: None,
+        }
+    }
+
+    /// Sets a configuration flag, such as `FLAG_WINDOW_RESIZABLE`.
+    pub fn flag(mut self, flag: ConfigFlags) -> Self {
+        self.flags |= flag as u32;
+        self
+    }
+
+    pub fn target_fps(mut self, fps: u32) -> Self {
+        self.target_fps = Some(fps);
+        self
+    }
+
+    /// Opens the window and creates its OpenGL context.
+    ///
+    /// Fails if a window is already open.
+    pub fn build(self) -> Result<Window> {
+        let title = CString::new(self.title)
+            .map_err(|e| Error::Io(io::Error::new(io::ErrorKind::InvalidInput, e)))?;
+
+        if OPEN.swap(true, Ordering::AcqRel) {
+            return Err(Error::InitWindow {
+                message: Some("a window is already open".to_owned()),
+            });
+        }
+
+        let (ready, mut messages) = log::capture(|| unsafe {
+            // GLFW 3.4 never picks the null platform on its own, even when
+            // it is the only one built, so it has to be requested.
+            #[cfg(feature = "headless")]
+            glfwInitHint(GLFW_PLATFORM, GLFW_PLATFORM_NULL);
+
+            sys::SetConfigFlags(self.flags);
+            sys::InitWindow(self.width, self.height, title.as_ptr());
+            sys::IsWindowReady()
+        });
+
+        if !ready {
+            OPEN.store(false, Ordering::Release);
+
+            return Err(Error::InitWindow {
+                message: messages.pop(),
+            });
+        }
+
+        if let Some(fps) = self.target_fps {
+            unsafe { sys::SetTargetFPS(fps as c_int) }
+        }
+
+        Ok(Window {
+            _marker: PhantomData,
+        })
+    }
+}
+
+/// The application window and its OpenGL context, closed when dropped.
+///
+/// GPU resources borrow the window they were created with, so they cannot
+/// outlive the context that owns them. The window is tied to the thread that
+/// opened it.
+#[derive(Debug)]
+pub struct Window {
+    _marker: PhantomData<*const ()>,
+}
+
+impl Window {
+    /// Opens a window with default settings.
+    pub fn new(width: i32, height: i32, title: impl Into<String>) -> Result<Self> {
+        WindowBuilder::new(width, height, title).build()
+    }
+
+    pub fn builder(width: i32, height: i32, title: impl Into<String>) -> WindowBuilder {
+        WindowBuilder::new(width, height, title)
+    }
+
+    /// Begins a frame. It is presented when the returned handle is dropped.
+    ///
+    /// Only one frame should be in progress at a time.
+    pub fn begin_drawing(&self) -> DrawHandle<'_> {
+        DrawHandle::new(self)
+    }
+
+    /// Whether the user has asked to close the window.
+    pub fn should_close(&self) -> bool {
+        unsafe { sys::WindowShouldClose() }
+    }
+
+    pub fn width(&self) -> i32 {
+        unsafe { sys::GetScreenWidth() }
+    }
+
+    pub fn height(&self) -> i32 {
+        unsafe { sys::GetScreenHeight() }
+    }
+
+    /// The framebuffer width, which differs from [`Self::width`] on high-DPI
+    /// displays.
+    pub fn render_width(&self) -> i32 {
+        unsafe { sys::GetRenderWidth() }
+    }
+
+    /// The framebuffer height, which differs from [`Self::height`] on
+    /// high-DPI displays.
+    pub fn render_height(&self) -> i32 {
+        unsafe { sys::GetRenderHeight() }
+    }
+
+    pub fn set_target_fps(&self, fps: u32) {
+        unsafe { sys::SetTargetFPS(fps as c_int) }
+    }
+
+    /// The time in seconds the last frame took.
+    pub fn frame_time(&self) -> f32 {
+        unsafe { sys::GetFrameTime() }
+    }
+}
+
+impl Drop for Window {
+    fn drop(&mut self) {
+        unsafe { sys::CloseWindow() }
+        OPEN.store(false, Ordering::Release);
+    }
+}
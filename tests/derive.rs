@@ -0,0 +1,8 @@
+#![cfg(feature = "derive")]
+
+#[test]
+fn shader_uniforms() {
+    let tests = trybuild::TestCases::new();
+    tests.pass("tests/ui/shader_uniforms_pass.rs");
+    tests.compile_fail("tests/ui/shader_uniforms_*_fail.rs");
+}
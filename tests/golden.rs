@@ -0,0 +1,46 @@
+//! Renders through the golden-image harness and compares against the PNGs
+//! checked in under `tests/golden`.
+//!
+//! This needs an OpenGL context, so it only runs by default with the
+//! `headless` feature. With a display, or under Xvfb, run it with
+//! `cargo test --test golden -- --ignored`.
+
+use rraylib::draw::Canvas;
+use rraylib::sys::Rectangle;
+use rraylib::testing::Golden;
+use rraylib::{BLACK, BLUE, RED};
+
+#[test]
+#[cfg_attr(
+    not(feature = "headless"),
+    ignore = "needs a display or the `headless` feature"
+)]
+fn squares() {
+    let golden = Golden::new(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden"))
+        .unwrap()
+        .with_tolerance(2);
+
+    // The blue bar in the top-left corner catches render textures being
+    // read back upside down.
+    golden.assert_matches("squares", 32, 32, |canvas| {
+        canvas.clear(BLACK);
+        canvas.draw_rectangle(
+            Rectangle {
+                x: 8.0,
+                y: 8.0,
+                width: 16.0,
+                height: 16.0,
+            },
+            RED,
+        );
+        canvas.draw_rectangle(
+            Rectangle {
+                x: 0.0,
+                y: 0.0,
+                width: 8.0,
+                height: 4.0,
+            },
+            BLUE,
+        );
+    });
+}
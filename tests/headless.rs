@@ -0,0 +1,56 @@
+//! Checks that the `headless` feature opens a window on GLFW's null platform
+//! and renders into it, with no display server.
+
+#![cfg(feature = "headless")]
+
+use rraylib::draw::Canvas;
+use rraylib::sys::{Color, Rectangle};
+use rraylib::texture::OwnedRenderTexture;
+use rraylib::window::Window;
+use rraylib::{BLUE, DARKGREEN, RED};
+
+fn rect(x: f32, y: f32, width: f32, height: f32) -> Rectangle {
+    Rectangle {
+        x,
+        y,
+        width,
+        height,
+    }
+}
+
+fn pixel(image: &rraylib::image::OwnedImage, x: i32, y: i32) -> Color {
+    image.get_pixel(x, y).unwrap()
+}
+
+#[test]
+fn draws_without_a_display() {
+    let window = Window::new(64, 48, "rraylib headless test").unwrap();
+
+    let mut target = OwnedRenderTexture::new(&window, 16, 8).unwrap();
+    {
+        let mut canvas = target.begin_drawing();
+        canvas.clear(RED);
+        canvas.draw_rectangle(rect(0.0, 0.0, 4.0, 2.0), BLUE);
+    }
+
+    let captured = target.capture().unwrap();
+    assert_eq!((captured.width(), captured.height()), (16, 8));
+    assert_eq!(pixel(&captured, 0, 0), BLUE);
+    assert_eq!(pixel(&captured, 3, 1), BLUE);
+    assert_eq!(pixel(&captured, 4, 0), RED);
+    assert_eq!(pixel(&captured, 15, 7), RED);
+
+    let mut frame = window.begin_drawing();
+    frame.clear(DARKGREEN);
+    frame.draw_rectangle(rect(0.0, 0.0, 10.0, 6.0), BLUE);
+
+    let screenshot = frame.screenshot().unwrap();
+    drop(frame);
+
+    let (width, height) = (window.render_width(), window.render_height());
+    assert_eq!((screenshot.width(), screenshot.height()), (width, height));
+    assert_eq!(pixel(&screenshot, 0, 0), BLUE);
+    assert_eq!(pixel(&screenshot, 9, 5), BLUE);
+    assert_eq!(pixel(&screenshot, 10, 0), DARKGREEN);
+    assert_eq!(pixel(&screenshot, width - 1, height - 1), DARKGREEN);
+}
@@ -0,0 +1,9 @@
+use rraylib::shader::ShaderUniforms;
+
+#[derive(ShaderUniforms)]
+struct Material {
+    #[uniform(rename = "u_tint")]
+    tint: f32,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use rraylib::shader::ShaderUniforms;
+
+#[derive(ShaderUniforms)]
+enum Mode {
+    Add,
+    Multiply,
+}
+
+fn main() {}
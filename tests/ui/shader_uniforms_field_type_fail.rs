@@ -0,0 +1,8 @@
+use rraylib::shader::ShaderUniforms;
+
+#[derive(ShaderUniforms)]
+struct Material {
+    label: String,
+}
+
+fn main() {}
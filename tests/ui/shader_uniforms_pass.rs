@@ -0,0 +1,23 @@
+use rraylib::shader::{OwnedShader, ShaderUniforms};
+use rraylib::sys::{Color, Vector2};
+
+#[derive(ShaderUniforms)]
+struct Material<'a> {
+    tint: Color,
+    #[uniform(name = "u_offset")]
+    offset: Vector2,
+    weights: &'a [f32],
+    #[uniform(skip)]
+    #[allow(dead_code)]
+    label: String,
+}
+
+fn apply(material: &Material<'_>, shader: &OwnedShader) -> rraylib::Result<()> {
+    let locations: [i32; 3] = Material::resolve(shader)?;
+    material.apply_resolved(shader, &locations);
+    material.apply(shader)
+}
+
+fn main() {
+    let _ = apply;
+}
@@ -0,0 +1,6 @@
+use rraylib::shader::ShaderUniforms;
+
+#[derive(ShaderUniforms)]
+struct Pair(f32, f32);
+
+fn main() {}